@@ -2,8 +2,18 @@
 
 use core::str::FromStr;
 
+#[cfg(feature = "serde")]
+use ibc_core::channel::types::channel::Order;
+#[cfg(feature = "serde")]
+use ibc_core::channel::types::error::ChannelError;
+#[cfg(feature = "serde")]
+use ibc_core::channel::types::events::SendPacket;
+#[cfg(feature = "serde")]
+use ibc_core::channel::types::packet::Packet;
+use ibc_core::channel::types::timeout::TimeoutHeight;
+use ibc_core::host::types::identifiers::{ChannelId, ConnectionId, PortId, Sequence};
 use ibc_core::primitives::prelude::*;
-use ibc_core::primitives::Signer;
+use ibc_core::primitives::{Signer, Timestamp};
 use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
 
 use super::error::TokenTransferError;
@@ -60,6 +70,56 @@ impl From<PacketData> for RawPacketData {
     }
 }
 
+/// The parameters of an ICS-20 token transfer, from which its packet and the
+/// corresponding `SendPacket` event are assembled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ics20SendParams {
+    pub seq_on_a: Sequence,
+    pub port_id_on_a: PortId,
+    pub chan_id_on_a: ChannelId,
+    pub port_id_on_b: PortId,
+    pub chan_id_on_b: ChannelId,
+    pub conn_id_on_a: ConnectionId,
+    pub token: PrefixedCoin,
+    pub sender: Signer,
+    pub receiver: Signer,
+    pub memo: Memo,
+    pub timeout_height_on_b: TimeoutHeight,
+    pub timeout_timestamp_on_b: Timestamp,
+}
+
+/// Serializes the ICS-20 packet data of a token transfer and assembles the
+/// `SendPacket` event of its packet. ICS-20 channels are always unordered.
+#[cfg(feature = "serde")]
+pub fn ics20_send_packet(params: Ics20SendParams) -> Result<SendPacket, ChannelError> {
+    let packet_data = PacketData {
+        token: params.token,
+        sender: params.sender,
+        receiver: params.receiver,
+        memo: params.memo,
+    };
+    let data = serde_json::to_vec(&packet_data).map_err(|e| ChannelError::Other {
+        description: format!("failed to serialize ICS-20 packet data: {e}"),
+    })?;
+
+    let packet = Packet {
+        seq_on_a: params.seq_on_a,
+        port_id_on_a: params.port_id_on_a,
+        chan_id_on_a: params.chan_id_on_a,
+        port_id_on_b: params.port_id_on_b,
+        chan_id_on_b: params.chan_id_on_b,
+        data,
+        timeout_height_on_b: params.timeout_height_on_b,
+        timeout_timestamp_on_b: params.timeout_timestamp_on_b,
+    };
+
+    Ok(SendPacket::new(
+        packet,
+        Order::Unordered,
+        params.conn_id_on_a,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use primitive_types::U256;
@@ -118,4 +178,46 @@ mod tests {
         PacketData::new_dummy().deser_json_assert_eq(dummy_json_packet_data());
         PacketData::new_dummy().deser_json_assert_eq(dummy_json_packet_data_without_memo());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ics20_send_packet_data_round_trip() {
+        let PacketData {
+            token,
+            sender,
+            receiver,
+            ..
+        } = PacketData::new_dummy();
+        let params = Ics20SendParams {
+            seq_on_a: 7u64.into(),
+            port_id_on_a: PortId::transfer(),
+            chan_id_on_a: ChannelId::zero(),
+            port_id_on_b: PortId::transfer(),
+            chan_id_on_b: ChannelId::new(1),
+            conn_id_on_a: ConnectionId::zero(),
+            token,
+            sender,
+            receiver,
+            memo: "memo".to_string().into(),
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b: Timestamp::from_nanoseconds(1_000).unwrap(),
+        };
+
+        let send_packet = ics20_send_packet(params.clone()).unwrap();
+        assert_eq!(send_packet.seq_on_a(), &params.seq_on_a);
+        assert_eq!(send_packet.chan_id_on_b(), &params.chan_id_on_b);
+        assert_eq!(send_packet.conn_id_on_a(), &params.conn_id_on_a);
+        assert!(send_packet.is_unordered());
+
+        let packet_data: PacketData = serde_json::from_slice(send_packet.packet_data()).unwrap();
+        assert_eq!(
+            packet_data,
+            PacketData {
+                token: params.token,
+                sender: params.sender,
+                receiver: params.receiver,
+                memo: params.memo,
+            }
+        );
+    }
 }
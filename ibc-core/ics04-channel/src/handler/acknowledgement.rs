@@ -162,6 +162,10 @@ where
             }
             .into());
         }
+
+        // Checked here rather than when storing the next sequence, so that
+        // execution cannot fail once it has changed the state.
+        next_seq_ack.increment_by(1).map_err(PacketError::from)?;
     }
 
     // Verify proofs
@@ -242,6 +242,10 @@ where
                 // Case where the recvPacket is successful and an
                 // acknowledgement will be written (not a no-op)
                 validate_write_acknowledgement(ctx_b, msg)?;
+
+                // Checked here rather than when storing the next sequence, so
+                // that execution cannot fail once it has changed the state.
+                next_seq_recv.increment_by(1).map_err(PacketError::from)?;
             }
         }
         Order::Unordered => {
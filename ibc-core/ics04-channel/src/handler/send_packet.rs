@@ -96,6 +96,12 @@ pub fn send_packet_validate(
         .into());
     }
 
+    // Checked here rather than when storing the next sequence, so that
+    // execution cannot fail once it has changed the state.
+    next_seq_send_on_a
+        .increment_by(1)
+        .map_err(PacketError::from)?;
+
     Ok(())
 }
 
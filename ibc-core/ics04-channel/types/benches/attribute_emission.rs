@@ -0,0 +1,97 @@
+//! Compares emitting the identifier attributes of a `send_packet` event by
+//! cloning them first, as needed by the by-value `From` conversions, against
+//! emitting them from a borrow with `to_attribute`.
+//!
+//! Both paths allocate the `String`s of the emitted attribute. Cloning also
+//! allocates a copy of the identifier, which is dropped right after being
+//! formatted, so `to_attribute` is expected to save one allocation and copy
+//! per attribute. Run with `cargo bench --bench attribute_emission` and
+//! compare the `clone` and `borrow` timings.
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ibc_core_channel_types::events::packet_attributes::{
+    DstChannelIdAttribute, DstPortIdAttribute, PacketConnectionIdAttribute, SrcChannelIdAttribute,
+    SrcPortIdAttribute,
+};
+use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId};
+use tendermint::abci;
+
+const EVENT_COUNT: u64 = 1_000;
+
+type IdentifierAttributes = (
+    SrcPortIdAttribute,
+    SrcChannelIdAttribute,
+    DstPortIdAttribute,
+    DstChannelIdAttribute,
+    PacketConnectionIdAttribute,
+);
+
+/// The identifier attributes of a block of `send_packet` events, with port
+/// identifiers as long as those of interchain accounts.
+fn identifier_attributes() -> Vec<IdentifierAttributes> {
+    let port_id: PortId = "icacontroller-cosmos1hsk6jryyqjfhp5dhc55tc9jtckygx0eph6dd02"
+        .parse()
+        .unwrap();
+    let counterparty_port_id: PortId = "icahost".parse().unwrap();
+
+    (0..EVENT_COUNT)
+        .map(|n| {
+            (
+                port_id.clone().into(),
+                ChannelId::new(n).into(),
+                counterparty_port_id.clone().into(),
+                ChannelId::new(n + 1).into(),
+                ConnectionId::new(n).into(),
+            )
+        })
+        .collect()
+}
+
+fn attribute_emission(c: &mut Criterion) {
+    let attributes = identifier_attributes();
+    let mut group = c.benchmark_group("attribute_emission");
+
+    group.bench_function("clone", |b| {
+        b.iter(|| {
+            attributes
+                .iter()
+                .map(
+                    |(src_port, src_channel, dst_port, dst_channel, connection)| {
+                        let emitted: [abci::EventAttribute; 5] = [
+                            src_port.clone().into(),
+                            src_channel.clone().into(),
+                            dst_port.clone().into(),
+                            dst_channel.clone().into(),
+                            connection.clone().into(),
+                        ];
+                        black_box(emitted).len()
+                    },
+                )
+                .sum::<usize>()
+        })
+    });
+
+    group.bench_function("borrow", |b| {
+        b.iter(|| {
+            attributes
+                .iter()
+                .map(
+                    |(src_port, src_channel, dst_port, dst_channel, connection)| {
+                        let emitted = [
+                            src_port.to_attribute(),
+                            src_channel.to_attribute(),
+                            dst_port.to_attribute(),
+                            dst_channel.to_attribute(),
+                            connection.to_attribute(),
+                        ];
+                        black_box(emitted).len()
+                    },
+                )
+                .sum::<usize>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, attribute_emission);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+//! Compares eager and lazy decoding of packet data over a stream of
+//! `send_packet` events of which only a few pass the channel filter.
+use criterion::{criterion_group, criterion_main, Criterion};
+use ibc_core_channel_types::channel::Order;
+use ibc_core_channel_types::events::{LazyPacketEvent, SendPacket};
+use ibc_core_channel_types::packet::Packet;
+use ibc_core_channel_types::timeout::TimeoutHeight;
+use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId};
+use ibc_primitives::Timestamp;
+use tendermint::abci;
+
+const EVENT_COUNT: u64 = 1_000;
+const CHANNEL_COUNT: u64 = 10;
+const PAYLOAD_SIZE: usize = 4_096;
+
+fn send_packet_events() -> Vec<abci::Event> {
+    (1..=EVENT_COUNT)
+        .map(|seq| {
+            let packet = Packet {
+                seq_on_a: seq.into(),
+                port_id_on_a: PortId::transfer(),
+                chan_id_on_a: ChannelId::new(seq % CHANNEL_COUNT),
+                port_id_on_b: PortId::transfer(),
+                chan_id_on_b: ChannelId::new(seq % CHANNEL_COUNT),
+                data: vec![b'a'; PAYLOAD_SIZE],
+                timeout_height_on_b: TimeoutHeight::Never,
+                timeout_timestamp_on_b: Timestamp::from_nanoseconds(1_000).unwrap(),
+            };
+
+            SendPacket::new(packet, Order::Unordered, ConnectionId::zero())
+                .try_into()
+                .unwrap()
+        })
+        .collect()
+}
+
+fn filtered_packet_data(c: &mut Criterion) {
+    let events = send_packet_events();
+    let wanted = ChannelId::new(0);
+
+    let mut group = c.benchmark_group("filtered_packet_data");
+
+    group.bench_function("eager", |b| {
+        b.iter(|| {
+            events
+                .iter()
+                .map(|event| SendPacket::try_from(event.clone()).unwrap())
+                .filter(|send_packet| send_packet.chan_id_on_a() == &wanted)
+                .map(|send_packet| send_packet.packet_data().len())
+                .sum::<usize>()
+        })
+    });
+
+    group.bench_function("lazy", |b| {
+        b.iter(|| {
+            events
+                .iter()
+                .map(|event| LazyPacketEvent::new(event.clone()))
+                .filter(|lazy| lazy.attribute("packet_src_channel") == Some(wanted.as_str()))
+                .map(|lazy| lazy.packet_data().unwrap().len())
+                .sum::<usize>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, filtered_packet_data);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+//! Feeds arbitrary attribute vectors, as received from remote chains, into
+//! the channel event parsers. Parsing must only ever fail with an error.
+//!
+//! The input is read as one `key=value` attribute per line, so that corpus
+//! entries remain human-readable. Keys and values are kept as raw bytes, in
+//! the byte-carrying attribute layout of CometBFT 0.34, so that non-UTF-8
+//! input reaches the parsers as is.
+#![no_main]
+
+use ibc_core_channel_types::events::packet_attributes::PacketDataAttribute;
+use ibc_core_channel_types::events::{
+    OpenAck, OpenConfirm, OpenInit, OpenTry, ReceivePacket, SendPacket,
+};
+use libfuzzer_sys::fuzz_target;
+use tendermint::abci;
+
+fuzz_target!(|data: &[u8]| {
+    let pairs: Vec<(&[u8], &[u8])> = data
+        .split(|&byte| byte == b'\n')
+        .map(|line| match line.iter().position(|&byte| byte == b'=') {
+            Some(separator) => (&line[..separator], &line[separator + 1..]),
+            None => (line, &[][..]),
+        })
+        .collect();
+
+    for &pair in &pairs {
+        if let Ok(attr) = PacketDataAttribute::try_from(pair) {
+            let _ = attr.redacted();
+            let _ = Vec::<abci::EventAttribute>::try_from(attr);
+        }
+    }
+
+    let attributes: Vec<abci::EventAttribute> = pairs
+        .iter()
+        .map(|(key, value)| {
+            abci::EventAttribute::V034(abci::v0_34::EventAttribute {
+                key: key.to_vec(),
+                value: value.to_vec(),
+                index: false,
+            })
+        })
+        .collect();
+
+    let _ = OpenInit::try_from(attributes.clone());
+    let _ = OpenTry::try_from(attributes.clone());
+    let _ = OpenAck::try_from(attributes.clone());
+    let _ = OpenConfirm::try_from(attributes.clone());
+
+    for kind in [SendPacket::kind(), ReceivePacket::kind()] {
+        let event = abci::Event::new(kind, attributes.clone());
+        let _ = SendPacket::try_from(event.clone()).and_then(abci::Event::try_from);
+        let _ = ReceivePacket::try_from(event).and_then(abci::Event::try_from);
+    }
+});
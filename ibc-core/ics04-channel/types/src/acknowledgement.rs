@@ -5,7 +5,7 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use derive_more::Into;
 use ibc_primitives::prelude::*;
 
-use super::error::PacketError;
+use super::error::{ChannelError, PacketError};
 
 /// A generic Acknowledgement type that modules may interpret as they like.
 ///
@@ -28,10 +28,90 @@ use super::error::PacketError;
 pub struct Acknowledgement(Vec<u8>);
 
 impl Acknowledgement {
+    /// Constructs an acknowledgement, rejecting empty ones as required by
+    /// ICS-04.
+    pub fn new(bytes: Vec<u8>) -> Result<Self, ChannelError> {
+        if bytes.is_empty() {
+            return Err(ChannelError::EmptyAcknowledgement);
+        }
+
+        Ok(Self(bytes))
+    }
+
+    /// Constructs an acknowledgement without checking that it is non-empty,
+    /// e.g. to exercise the handling of malformed acknowledgements in tests.
+    #[cfg(any(test, feature = "testkit"))]
+    pub fn new_unchecked(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
     // Returns the data as a slice of bytes.
     pub fn as_bytes(&self) -> &[u8] {
         self.0.as_slice()
     }
+
+    /// Classifies the acknowledgement by the standard JSON envelope used by
+    /// ICS-20, i.e. `{"result":"..."}` on success and `{"error":"..."}` on
+    /// failure. Any other acknowledgement, e.g. a binary one, is
+    /// [`AcknowledgementShape::Unknown`].
+    ///
+    /// Only envelopes with unescaped keys are recognized, whatever the
+    /// enabled features, so that the same bytes are always classified alike.
+    pub fn shape(&self) -> AcknowledgementShape {
+        core::str::from_utf8(self.as_bytes())
+            .ok()
+            .and_then(parse_envelope)
+            .unwrap_or(AcknowledgementShape::Unknown)
+    }
+}
+
+/// The shape of an [`Acknowledgement`], as recognized by
+/// [`Acknowledgement::shape`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AcknowledgementShape {
+    /// A `{"result":"..."}` acknowledgement.
+    Success,
+    /// An `{"error":"..."}` acknowledgement.
+    Error,
+    /// An acknowledgement not wrapped in the standard JSON envelope.
+    Unknown,
+}
+
+/// Parses a `{"<key>":"<value>"}` envelope, allowing whitespace between its
+/// tokens, and classifies it by its key.
+fn parse_envelope(ack: &str) -> Option<AcknowledgementShape> {
+    let body = ack.trim().strip_prefix('{')?.strip_suffix('}')?.trim();
+    let (key, rest) = split_json_string(body)?;
+    let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+    let (_, rest) = split_json_string(rest)?;
+
+    if !rest.trim().is_empty() {
+        return None;
+    }
+
+    match key {
+        "result" => Some(AcknowledgementShape::Success),
+        "error" => Some(AcknowledgementShape::Error),
+        _ => None,
+    }
+}
+
+/// Splits the JSON string literal at the start of `s` into its raw contents,
+/// escapes left as is, and the remainder of `s`.
+fn split_json_string(s: &str) -> Option<(&str, &str)> {
+    let s = s.strip_prefix('"')?;
+    let mut escaped = false;
+
+    for (index, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some((&s[..index], &s[index + 1..])),
+            _ => {}
+        }
+    }
+
+    None
 }
 
 impl AsRef<[u8]> for Acknowledgement {
@@ -139,3 +219,65 @@ impl From<AcknowledgementStatus> for Acknowledgement {
             .expect("token transfer internal error: ack is never supposed to be empty")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_rejects_empty_acknowledgement() {
+        assert!(matches!(
+            Acknowledgement::new(Vec::new()),
+            Err(ChannelError::EmptyAcknowledgement)
+        ));
+        assert_eq!(Acknowledgement::new_unchecked(Vec::new()).as_bytes(), b"");
+    }
+
+    #[test]
+    fn new_accepts_non_empty_acknowledgement() {
+        let ack = Acknowledgement::new(b"ack".to_vec()).unwrap();
+
+        assert_eq!(ack.as_bytes(), b"ack");
+        assert_eq!(ack, Acknowledgement::try_from(b"ack".to_vec()).unwrap());
+    }
+
+    #[test]
+    fn shape_of_success_acknowledgement() {
+        let ack = Acknowledgement::new(br#"{"result":"AQ=="}"#.to_vec()).unwrap();
+        assert_eq!(ack.shape(), AcknowledgementShape::Success);
+
+        let spaced = Acknowledgement::new(br#" { "result" : "AQ==" } "#.to_vec()).unwrap();
+        assert_eq!(spaced.shape(), AcknowledgementShape::Success);
+
+        let status = AcknowledgementStatus::success(StatusValue::new("AQ==").unwrap());
+        assert_eq!(
+            Acknowledgement::from(status).shape(),
+            AcknowledgementShape::Success
+        );
+    }
+
+    #[test]
+    fn shape_of_error_acknowledgement() {
+        let ack = Acknowledgement::new(
+            br#"{"error":"cannot unmarshal ICS-20 transfer packet data: \"amount\""}"#.to_vec(),
+        )
+        .unwrap();
+        assert_eq!(ack.shape(), AcknowledgementShape::Error);
+    }
+
+    #[test]
+    fn shape_of_non_envelope_acknowledgement() {
+        for bytes in [
+            &[0x01, 0xff][..],
+            b"ack",
+            br#"{"other":"AQ=="}"#,
+            br#"{"result":"AQ==","error":"e"}"#,
+            br#"{"result":1}"#,
+            br#"{"result":"AQ=="#,
+            br#"{"\u0072esult":"AQ=="}"#,
+        ] {
+            let ack = Acknowledgement::new(bytes.to_vec()).unwrap();
+            assert_eq!(ack.shape(), AcknowledgementShape::Unknown, "{bytes:?}");
+        }
+    }
+}
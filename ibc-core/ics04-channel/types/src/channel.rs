@@ -474,6 +474,11 @@ impl Order {
         }
     }
 
+    /// Yields the Order as its protobuf enum value
+    pub fn as_proto_i32(&self) -> i32 {
+        *self as i32
+    }
+
     // Parses the Order out from a i32.
     pub fn from_i32(nr: i32) -> Result<Self, ChannelError> {
         match nr {
@@ -4,6 +4,7 @@ use ibc_primitives::prelude::*;
 use ibc_primitives::Timestamp;
 
 use super::acknowledgement::Acknowledgement;
+use crate::error::ChannelError;
 use crate::timeout::TimeoutHeight;
 
 /// Packet commitment
@@ -104,6 +105,17 @@ pub fn compute_ack_commitment(ack: &Acknowledgement) -> AcknowledgementCommitmen
     hash(ack.as_ref()).to_vec().into()
 }
 
+/// Checks an acknowledgement, e.g. taken from a `WriteAcknowledgement`
+/// event, against the acknowledgement commitment stored by the destination
+/// chain.
+pub fn verify_ack_commitment(ack: &Acknowledgement, commitment: &[u8]) -> Result<(), ChannelError> {
+    if compute_ack_commitment(ack).as_ref() != commitment {
+        return Err(ChannelError::AckCommitmentMismatch);
+    }
+
+    Ok(())
+}
+
 /// Helper function to hash a byte slice using SHA256.
 ///
 /// Note that computing commitments with anything apart from SHA256 will
@@ -144,4 +156,25 @@ mod test {
         let actual = compute_ack_commitment(&ack);
         assert_eq!(&expected[..], actual.as_ref())
     }
+
+    #[test]
+    fn test_verify_ack_commitment() {
+        let ack = Acknowledgement::try_from(vec![0, 1, 2, 3]).unwrap();
+        let commitment = compute_ack_commitment(&ack);
+
+        assert!(verify_ack_commitment(&ack, commitment.as_ref()).is_ok());
+
+        let tampered_ack = Acknowledgement::try_from(vec![0, 1, 2, 4]).unwrap();
+        assert!(matches!(
+            verify_ack_commitment(&tampered_ack, commitment.as_ref()),
+            Err(ChannelError::AckCommitmentMismatch)
+        ));
+
+        let mut tampered_commitment = commitment.into_vec();
+        tampered_commitment[0] ^= 1;
+        assert!(matches!(
+            verify_ack_commitment(&ack, &tampered_commitment),
+            Err(ChannelError::AckCommitmentMismatch)
+        ));
+    }
 }
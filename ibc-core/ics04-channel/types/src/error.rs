@@ -1,7 +1,9 @@
 //! Defines the main channel, port, and packet error types
 
+use core::convert::Infallible;
+
 use displaydoc::Display;
-use ibc_core_client_types::{error as client_error, Height};
+use ibc_core_client_types::{error as client_error, Height, HeightError};
 use ibc_core_connection_types::error as connection_error;
 use ibc_core_host_types::error::IdentifierError;
 use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId, Sequence};
@@ -10,7 +12,7 @@ use ibc_primitives::{ParseTimestampError, Timestamp};
 
 use super::channel::Counterparty;
 use super::timeout::TimeoutHeight;
-use crate::channel::State;
+use crate::channel::{Order, State};
 use crate::Version;
 
 #[derive(Debug, Display)]
@@ -31,6 +33,8 @@ pub enum ChannelError {
     MissingHeight,
     /// packet data bytes must be valid UTF-8 (this restriction will be lifted in the future)
     NonUtf8PacketData,
+    /// acknowledgement cannot be empty
+    EmptyAcknowledgement,
     /// missing counterparty
     MissingCounterparty,
     /// unsupported channel upgrade sequence
@@ -44,6 +48,13 @@ pub enum ChannelError {
         port_id: PortId,
         channel_id: ChannelId,
     },
+    /// no application is registered for port `{port_id}`
+    UnknownPort { port_id: PortId },
+    /// channel `{channel_id}` uses ordering `{ordering}` not supported by its connection
+    OrderingNotSupported {
+        channel_id: ChannelId,
+        ordering: Order,
+    },
     /// Verification fails for the packet with the sequence number `{sequence}`, error: `{client_error}`
     PacketVerificationFailed {
         sequence: Sequence,
@@ -74,13 +85,152 @@ pub enum ChannelError {
     /// invalid attribute key: `{attribute_key}`
     InvalidAttributeKey { attribute_key: String },
     /// invalid attribute value: `{attribute_value}`
-    InvalidAttributeValue { attribute_value: String },
+    InvalidAttributeValue {
+        attribute_value: String,
+        error: Option<AttributeValueError>,
+    },
+    /// invalid hex attribute value `{attribute_value}`: `{error}`
+    InvalidHexAttributeValue {
+        attribute_value: String,
+        error: HexDecodeError,
+    },
+    /// hex attribute `{attribute_key}` uses a different letter case than the other hex attributes
+    InconsistentHexCasing { attribute_key: String },
+    /// event `{event_kind}` at position `{position}` is not attributed to any message
+    OrphanedEvent { event_kind: String, position: usize },
+    /// event `{event_kind}` is attributed to message `{msg_index}`, but the transaction has `{num_msgs}` messages
+    EventMsgIndexOutOfRange {
+        event_kind: String,
+        msg_index: u32,
+        num_msgs: usize,
+    },
+    /// channel identifier `{channel_id}` is not in the canonical `channel-<N>` form
+    NonCanonicalChannelId { channel_id: String },
+    /// channel ordering mismatch: send packet reports `{send_ordering}`, receive packet reports `{recv_ordering}`
+    OrderingMismatch {
+        send_ordering: Order,
+        recv_ordering: Order,
+    },
+    /// event reports channel ordering `{actual}`, but the channel end is `{expected}`
+    ChannelEndOrderingMismatch { expected: Order, actual: Order },
+    /// packet sequence `{sequence}` is beyond the highest expected sequence `{max_expected}`
+    SequenceOutOfRange {
+        sequence: Sequence,
+        max_expected: Sequence,
+    },
+    /// plaintext `packet_data` is truncated: `{plaintext_len}` bytes out of the `{hex_len}` bytes of `packet_data_hex`
+    TruncatedPlaintextData {
+        plaintext_len: usize,
+        hex_len: usize,
+    },
+    /// plaintext `packet_data` extends past the end of the decoded `packet_data_hex`: `{plaintext_len}` bytes instead of `{hex_len}`
+    OversizedPlaintextData {
+        plaintext_len: usize,
+        hex_len: usize,
+    },
+    /// plaintext `packet_data` first differs from the decoded `packet_data_hex` at byte `{offset}` (`{plaintext_len}` and `{hex_len}` bytes long)
+    PacketDataContentMismatch {
+        offset: usize,
+        plaintext_len: usize,
+        hex_len: usize,
+    },
+    /// timeout height `{timeout_height}` is not on the destination chain revision `{dst_revision}`
+    TimeoutRevisionMismatch {
+        timeout_height: TimeoutHeight,
+        dst_revision: u64,
+    },
+    /// packet has neither a timeout height nor a timeout timestamp
+    MissingTimeout,
+    /// packet timeout height `{timeout_height}` or timeout timestamp `{timeout_timestamp}` has elapsed on the destination chain at height `{dst_height}` and time `{dst_time}`
+    ElapsedTimeout {
+        timeout_height: TimeoutHeight,
+        timeout_timestamp: Timestamp,
+        dst_height: Height,
+        dst_time: Timestamp,
+    },
+    /// acknowledgement does not match the stored acknowledgement commitment
+    AckCommitmentMismatch,
+    /// failed to convert event at index `{index}`: {error}
+    EventConversionFailed {
+        index: usize,
+        error: Box<ChannelError>,
+    },
     /// Missing attribute key: `{attribute_key}`
     MissingAttributeKey { attribute_key: String },
+    /// unexpected counterparty channel id `{counterparty_channel_id}` in a channel open init event
+    UnexpectedCounterpartyChannelId { counterparty_channel_id: String },
+    /// attribute `{attribute_key}` is not flagged as indexed
+    NonIndexedAttribute { attribute_key: String },
+    /// duplicate attribute key: `{attribute_key}`
+    DuplicateAttributeKey { attribute_key: String },
     /// other error: `{description}`
     Other { description: String },
 }
 
+/// The underlying error that made an attribute value unparsable
+#[derive(Debug, Display)]
+pub enum AttributeValueError {
+    /// value is not valid UTF-8: `{0}`
+    Utf8(core::str::Utf8Error),
+    /// invalid integer: `{0}`
+    Integer(core::num::ParseIntError),
+    /// invalid height: `{0}`
+    Height(HeightError),
+    /// invalid timestamp: `{0}`
+    Timestamp(ParseTimestampError),
+    /// invalid identifier: `{0}`
+    Identifier(IdentifierError),
+    /// invalid channel ordering: `{0}`
+    Ordering(Box<ChannelError>),
+    /// invalid acknowledgement: `{0}`
+    Acknowledgement(Box<PacketError>),
+}
+
+impl From<core::str::Utf8Error> for AttributeValueError {
+    fn from(err: core::str::Utf8Error) -> Self {
+        Self::Utf8(err)
+    }
+}
+
+impl From<core::num::ParseIntError> for AttributeValueError {
+    fn from(err: core::num::ParseIntError) -> Self {
+        Self::Integer(err)
+    }
+}
+
+impl From<HeightError> for AttributeValueError {
+    fn from(err: HeightError) -> Self {
+        Self::Height(err)
+    }
+}
+
+impl From<ParseTimestampError> for AttributeValueError {
+    fn from(err: ParseTimestampError) -> Self {
+        Self::Timestamp(err)
+    }
+}
+
+impl From<IdentifierError> for AttributeValueError {
+    fn from(err: IdentifierError) -> Self {
+        Self::Identifier(err)
+    }
+}
+
+impl From<Infallible> for AttributeValueError {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
+}
+
+/// Reasons why a hex-encoded attribute value could not be decoded
+#[derive(Clone, Debug, Display, PartialEq, Eq)]
+pub enum HexDecodeError {
+    /// odd number of hex digits: `{length}`
+    OddLength { length: usize },
+    /// invalid hex character `{character}` at position `{position}`
+    InvalidCharacter { character: char, position: usize },
+}
+
 #[derive(Debug, Display)]
 pub enum PacketError {
     /// connection error: `{0}`
@@ -162,6 +312,10 @@ pub enum PacketError {
     },
     /// Commitment for the packet `{sequence}` not found
     PacketCommitmentNotFound { sequence: Sequence },
+    /// malformed packet id key: `{reason}`
+    MalformedPacketIdKey { reason: String },
+    /// invalid sequence range: end `{end}` < start `{start}`
+    InvalidSequenceRange { start: Sequence, end: Sequence },
     /// Missing sequence number for receiving packets on port `{port_id}` and channel `{channel_id}`
     MissingNextRecvSeq {
         port_id: PortId,
@@ -194,6 +348,7 @@ impl std::error::Error for PacketError {
         match &self {
             Self::Connection(e) => Some(e),
             Self::Channel(e) => Some(e),
+            Self::InvalidPacketTimestamp(e) => Some(e),
             Self::InvalidIdentifier(e) => Some(e),
             _ => None,
         }
@@ -208,8 +363,38 @@ impl std::error::Error for ChannelError {
             Self::PacketVerificationFailed {
                 client_error: e, ..
             } => Some(e),
+            Self::VerifyChannelFailed(e) => Some(e),
             Self::InvalidStringAsSequence { error: e, .. } => Some(e),
+            Self::InvalidAttributeValue { error: Some(e), .. } => Some(e.inner()),
+            Self::InvalidHexAttributeValue { error: e, .. } => Some(e),
+            Self::EventConversionFailed { error: e, .. } => Some(e.as_ref()),
             _ => None,
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl AttributeValueError {
+    /// The parse error this error wraps.
+    fn inner(&self) -> &(dyn std::error::Error + 'static) {
+        match self {
+            Self::Utf8(e) => e,
+            Self::Integer(e) => e,
+            Self::Height(e) => e,
+            Self::Timestamp(e) => e,
+            Self::Identifier(e) => e,
+            Self::Ordering(e) => e.as_ref(),
+            Self::Acknowledgement(e) => e.as_ref(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AttributeValueError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.inner())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HexDecodeError {}
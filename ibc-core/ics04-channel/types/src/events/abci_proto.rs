@@ -0,0 +1,84 @@
+//! Encoding of event attributes in the exact protobuf wire format of
+//! `tendermint.abci.EventAttribute`, for embedding events into other protobuf
+//! messages.
+use ibc_primitives::prelude::*;
+use prost::encoding::message;
+use tendermint::abci;
+use tendermint_proto::v0_38::abci::EventAttribute as RawEventAttribute;
+
+use super::invalid_attribute_value;
+use crate::error::ChannelError;
+
+/// The field number of the `attributes` field of `tendermint.abci.Event`.
+pub const ABCI_EVENT_ATTRIBUTES_FIELD: u32 = 2;
+
+/// Encodes the attributes of `event` as the repeated `EventAttribute` field
+/// `field_number` of a protobuf message, ready to be spliced into its bytes.
+///
+/// With [`ABCI_EVENT_ATTRIBUTES_FIELD`], appending the output to the encoded
+/// event kind yields the encoding of the whole `tendermint.abci.Event`.
+///
+/// Fails if a key or value is not valid UTF-8, as the protobuf fields are
+/// strings.
+pub fn encode_attributes(event: &abci::Event, field_number: u32) -> Result<Vec<u8>, ChannelError> {
+    let mut buf = Vec::new();
+
+    for attribute in &event.attributes {
+        let raw = RawEventAttribute {
+            key: attribute
+                .key_str()
+                .map_err(|_| ChannelError::InvalidAttributeKey {
+                    attribute_key: String::new(),
+                })?
+                .to_string(),
+            value: core::str::from_utf8(attribute.value_bytes())
+                .map_err(|e| invalid_attribute_value("", e))?
+                .to_string(),
+            index: attribute.index(),
+        };
+        message::encode(field_number, &raw, &mut buf);
+    }
+
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+    use tendermint_proto::v0_38::abci::Event as RawEvent;
+
+    use super::*;
+
+    #[test]
+    fn attributes_proto_round_trip() {
+        let event = abci::Event {
+            kind: "send_packet".to_string(),
+            attributes: vec![
+                ("packet_sequence", "1", true).into(),
+                ("packet_src_port", "transfer", false).into(),
+                ("packet_data", "", true).into(),
+            ],
+        };
+
+        let bytes = encode_attributes(&event, ABCI_EVENT_ATTRIBUTES_FIELD).unwrap();
+        let raw = RawEvent::decode(bytes.as_slice()).unwrap();
+
+        assert!(raw.r#type.is_empty());
+        let attributes: Vec<abci::EventAttribute> = raw
+            .attributes
+            .into_iter()
+            .map(|attr| abci::EventAttribute::from((attr.key, attr.value, attr.index)))
+            .collect();
+        assert_eq!(attributes, event.attributes);
+
+        let mut spliced = RawEvent {
+            r#type: event.kind.clone(),
+            attributes: Vec::new(),
+        }
+        .encode_to_vec();
+        spliced.extend_from_slice(&bytes);
+        let raw = RawEvent::decode(spliced.as_slice()).unwrap();
+        assert_eq!(raw.r#type, event.kind);
+        assert_eq!(raw.attributes.len(), 3);
+    }
+}
@@ -0,0 +1,157 @@
+//! `proptest` generators of valid attributes, for downstream crates driving
+//! property tests with channel events.
+use ibc_core_client_types::Height;
+use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId, Sequence};
+use ibc_primitives::Timestamp;
+use proptest::prelude::*;
+
+use super::channel_attributes::{
+    ChannelIdAttribute, ConnectionIdAttribute, CounterpartyChannelIdAttribute,
+    CounterpartyPortIdAttribute, PortIdAttribute, VersionAttribute,
+};
+use super::packet_attributes::{
+    AcknowledgementAttribute, ChannelOrderingAttribute, DstChannelIdAttribute, DstPortIdAttribute,
+    PacketConnectionIdAttribute, PacketDataAttribute, SequenceAttribute, SrcChannelIdAttribute,
+    SrcPortIdAttribute, TimeoutHeightAttribute, TimeoutTimestampAttribute,
+};
+use crate::acknowledgement::Acknowledgement;
+use crate::channel::Order;
+use crate::timeout::TimeoutHeight;
+use crate::Version;
+
+fn port_id() -> impl Strategy<Value = PortId> {
+    "[a-z][a-z0-9]{1,31}".prop_filter_map("invalid port identifier", |id| PortId::new(id).ok())
+}
+
+fn channel_id() -> impl Strategy<Value = ChannelId> {
+    any::<u64>().prop_map(ChannelId::new)
+}
+
+fn connection_id() -> impl Strategy<Value = ConnectionId> {
+    any::<u64>().prop_map(ConnectionId::new)
+}
+
+fn timeout_height() -> impl Strategy<Value = TimeoutHeight> {
+    prop_oneof![
+        Just(TimeoutHeight::Never),
+        (any::<u64>(), 1..=u64::MAX).prop_filter_map(
+            "invalid height",
+            |(revision_number, revision_height)| {
+                Height::new(revision_number, revision_height)
+                    .ok()
+                    .map(TimeoutHeight::At)
+            }
+        ),
+    ]
+}
+
+fn timestamp() -> impl Strategy<Value = Timestamp> {
+    prop_oneof![Just(0u64), any::<u64>()].prop_filter_map("invalid timestamp", |nanoseconds| {
+        Timestamp::from_nanoseconds(nanoseconds).ok()
+    })
+}
+
+/// Packet events are only emitted on initialized channels.
+fn order() -> impl Strategy<Value = Order> {
+    prop_oneof![Just(Order::Unordered), Just(Order::Ordered)]
+}
+
+/// Implements `Arbitrary` for attributes by mapping the given strategy.
+macro_rules! impl_arbitrary {
+    ($($attribute:ty => $strategy:expr),+ $(,)?) => {
+        $(
+            impl Arbitrary for $attribute {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                    $strategy.prop_map(Self::from).boxed()
+                }
+            }
+        )+
+    };
+}
+
+impl_arbitrary!(
+    PacketDataAttribute => proptest::collection::vec(any::<u8>(), 1..64),
+    TimeoutHeightAttribute => timeout_height(),
+    TimeoutTimestampAttribute => timestamp(),
+    SequenceAttribute => (1..=u64::MAX).prop_map(Sequence::from),
+    SrcPortIdAttribute => port_id(),
+    SrcChannelIdAttribute => channel_id(),
+    DstPortIdAttribute => port_id(),
+    DstChannelIdAttribute => channel_id(),
+    ChannelOrderingAttribute => order(),
+    PacketConnectionIdAttribute => connection_id(),
+    AcknowledgementAttribute => proptest::collection::vec(any::<u8>(), 1..64)
+        .prop_filter_map("empty acknowledgement", |ack| Acknowledgement::new(ack).ok()),
+    PortIdAttribute => port_id(),
+    ChannelIdAttribute => channel_id(),
+    CounterpartyPortIdAttribute => port_id(),
+    CounterpartyChannelIdAttribute => channel_id(),
+    ConnectionIdAttribute => connection_id(),
+    VersionAttribute => "[a-z0-9-]{0,16}".prop_map(Version::new),
+);
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+
+    use tendermint::abci;
+
+    use super::*;
+    use crate::error::ChannelError;
+
+    fn round_trip<T>(attr: T)
+    where
+        T: Clone + Debug + PartialEq + Into<abci::EventAttribute>,
+        T: TryFrom<abci::EventAttribute, Error = ChannelError>,
+    {
+        let emitted: abci::EventAttribute = attr.clone().into();
+        assert_eq!(T::try_from(emitted).unwrap(), attr);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100))]
+
+        #[test]
+        fn arbitrary_attributes_round_trip(
+            timeout_height in any::<TimeoutHeightAttribute>(),
+            timeout_timestamp in any::<TimeoutTimestampAttribute>(),
+            sequence in any::<SequenceAttribute>(),
+            src_port_id in any::<SrcPortIdAttribute>(),
+            src_channel_id in any::<SrcChannelIdAttribute>(),
+            dst_port_id in any::<DstPortIdAttribute>(),
+            dst_channel_id in any::<DstChannelIdAttribute>(),
+            ordering in any::<ChannelOrderingAttribute>(),
+            connection_id in any::<PacketConnectionIdAttribute>(),
+            port_id in any::<PortIdAttribute>(),
+            channel_id in any::<ChannelIdAttribute>(),
+            counterparty_port_id in any::<CounterpartyPortIdAttribute>(),
+            counterparty_channel_id in any::<CounterpartyChannelIdAttribute>(),
+            handshake_connection_id in any::<ConnectionIdAttribute>(),
+            version in any::<VersionAttribute>(),
+            packet_data in any::<PacketDataAttribute>(),
+            acknowledgement in any::<AcknowledgementAttribute>(),
+        ) {
+            round_trip(timeout_height);
+            round_trip(timeout_timestamp);
+            round_trip(sequence);
+            round_trip(src_port_id);
+            round_trip(src_channel_id);
+            round_trip(dst_port_id);
+            round_trip(dst_channel_id);
+            round_trip(ordering);
+            round_trip(connection_id);
+            round_trip(port_id);
+            round_trip(channel_id);
+            round_trip(counterparty_port_id);
+            round_trip(counterparty_channel_id);
+            round_trip(handshake_connection_id);
+            round_trip(version);
+
+            prop_assert!(!packet_data.packet_data.is_empty());
+            prop_assert!(acknowledgement.validate().is_ok());
+        }
+    }
+}
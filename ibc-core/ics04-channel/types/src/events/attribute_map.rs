@@ -0,0 +1,195 @@
+//! Key-based access to the attributes of an event, shared by the parsing and
+//! emission of the attribute types tied to a single ABCI attribute key.
+use alloc::borrow::Cow;
+
+use ibc_primitives::prelude::*;
+use tendermint::abci;
+
+use super::{attribute_key_matches, missing_attribute_key, record_parse_error};
+use crate::error::ChannelError;
+
+/// An attribute type emitted as, and parsed from, the single ABCI attribute
+/// with key [`AttributeKey::KEY`].
+pub(super) trait AttributeKey:
+    TryFrom<abci::EventAttribute, Error = ChannelError> + Into<abci::EventAttribute>
+{
+    const KEY: &'static str;
+
+    /// Whether an attribute with an empty value stands for a missing one, as
+    /// for values not known yet when the event is emitted.
+    const EMPTY_IS_ABSENT: bool = false;
+}
+
+/// The attributes of an event, either borrowed for parsing or owned for
+/// emission, looked up by the key of their attribute type.
+#[derive(Clone, Debug, Default)]
+pub(super) struct AttributeMap<'a> {
+    attributes: Cow<'a, [abci::EventAttribute]>,
+}
+
+impl<'a> AttributeMap<'a> {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the attribute of type `T`, failing with
+    /// [`ChannelError::MissingAttributeKey`] if there is none.
+    pub(super) fn get<T: AttributeKey>(&self) -> Result<T, ChannelError> {
+        self.get_optional()?
+            .ok_or_else(|| missing_attribute_key(T::KEY))
+    }
+
+    /// Parses the attribute of type `T`, if any, failing with
+    /// [`ChannelError::DuplicateAttributeKey`] if its key is repeated.
+    pub(super) fn get_optional<T: AttributeKey>(&self) -> Result<Option<T>, ChannelError> {
+        self.get_raw::<T>()?
+            .map(|attribute| T::try_from(attribute.clone()))
+            .transpose()
+    }
+
+    /// Looks up the attribute of type `T` without parsing it, e.g. to reject
+    /// an attribute that must be absent whatever its value.
+    pub(super) fn get_raw<T: AttributeKey>(
+        &self,
+    ) -> Result<Option<&abci::EventAttribute>, ChannelError> {
+        Ok(self
+            .find(T::KEY)?
+            .filter(|attribute| !(T::EMPTY_IS_ABSENT && attribute.value_bytes().is_empty())))
+    }
+
+    /// Parses the attribute with the given key, if any, for attribute types
+    /// parsed from one of several keys, e.g. the hex-encoded and plaintext
+    /// forms of the packet data.
+    pub(super) fn get_keyed<T>(&self, key: &str) -> Result<Option<T>, ChannelError>
+    where
+        T: TryFrom<abci::EventAttribute, Error = ChannelError>,
+    {
+        self.find(key)?
+            .map(|attribute| T::try_from(attribute.clone()))
+            .transpose()
+    }
+
+    /// Appends the attribute of type `T`, failing with
+    /// [`ChannelError::DuplicateAttributeKey`] if its key is already present.
+    pub(super) fn insert<T: AttributeKey>(&mut self, attribute: T) -> Result<(), ChannelError> {
+        if self.find(T::KEY)?.is_some() {
+            return Err(ChannelError::DuplicateAttributeKey {
+                attribute_key: T::KEY.to_string(),
+            });
+        }
+
+        self.attributes.to_mut().push(attribute.into());
+
+        Ok(())
+    }
+
+    /// Appends raw attributes, e.g. those of attribute types emitted as
+    /// several ABCI attributes.
+    pub(super) fn extend(&mut self, attributes: Vec<abci::EventAttribute>) {
+        self.attributes.to_mut().extend(attributes);
+    }
+
+    pub(super) fn into_attributes(self) -> Vec<abci::EventAttribute> {
+        self.attributes.into_owned()
+    }
+
+    /// Looks up the attribute with the given key, failing with
+    /// [`ChannelError::DuplicateAttributeKey`] if there are several, as
+    /// conflicting values cannot be told apart.
+    pub(super) fn find(&self, key: &str) -> Result<Option<&abci::EventAttribute>, ChannelError> {
+        let mut matching = self.attributes.iter().filter(|attribute| {
+            attribute
+                .key_str()
+                .is_ok_and(|attribute_key| attribute_key_matches(attribute_key, key))
+        });
+
+        let attribute = matching.next();
+        if matching.next().is_some() {
+            return Err(record_parse_error(ChannelError::DuplicateAttributeKey {
+                attribute_key: key.to_string(),
+            }));
+        }
+
+        Ok(attribute)
+    }
+}
+
+impl<'a> From<&'a [abci::EventAttribute]> for AttributeMap<'a> {
+    fn from(attributes: &'a [abci::EventAttribute]) -> Self {
+        Self {
+            attributes: Cow::Borrowed(attributes),
+        }
+    }
+}
+
+impl From<Vec<abci::EventAttribute>> for AttributeMap<'_> {
+    fn from(attributes: Vec<abci::EventAttribute>) -> Self {
+        Self {
+            attributes: Cow::Owned(attributes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_core_host_types::identifiers::{ChannelId, PortId};
+
+    use super::*;
+    use crate::events::channel_attributes::{CounterpartyChannelIdAttribute, PortIdAttribute};
+
+    #[test]
+    fn get_missing_attribute() {
+        let map = AttributeMap::new();
+
+        assert!(matches!(
+            map.get::<PortIdAttribute>(),
+            Err(ChannelError::MissingAttributeKey { attribute_key })
+                if attribute_key == "port_id"
+        ));
+        assert_eq!(map.get_optional::<PortIdAttribute>().unwrap(), None);
+    }
+
+    #[test]
+    fn get_empty_attribute_of_unknown_value() {
+        let attributes = vec![("counterparty_channel_id", "").into()];
+        let map = AttributeMap::from(attributes.as_slice());
+
+        assert!(matches!(
+            map.get::<CounterpartyChannelIdAttribute>(),
+            Err(ChannelError::MissingAttributeKey { .. })
+        ));
+    }
+
+    #[test]
+    fn insert_duplicate_attribute() {
+        let mut map = AttributeMap::new();
+        map.insert(PortIdAttribute::from(PortId::transfer()))
+            .unwrap();
+
+        assert!(matches!(
+            map.insert(PortIdAttribute::from(PortId::transfer())),
+            Err(ChannelError::DuplicateAttributeKey { attribute_key })
+                if attribute_key == "port_id"
+        ));
+        map.insert(CounterpartyChannelIdAttribute::from(ChannelId::zero()))
+            .unwrap();
+
+        assert_eq!(
+            map.get::<PortIdAttribute>().unwrap().port_id,
+            PortId::transfer()
+        );
+        assert_eq!(map.into_attributes().len(), 2);
+    }
+
+    #[test]
+    fn get_repeated_attribute() {
+        let attributes = vec![("port_id", "transfer").into(), ("port_id", "other").into()];
+        let map = AttributeMap::from(attributes.as_slice());
+
+        assert!(matches!(
+            map.get::<PortIdAttribute>(),
+            Err(ChannelError::DuplicateAttributeKey { attribute_key })
+                if attribute_key == "port_id"
+        ));
+    }
+}
@@ -1,24 +1,33 @@
 //! This module holds all the abci event attributes for IBC events emitted
 //! during the channel handshake.
-use alloc::string::ToString;
+//!
+//! As for packet attributes, each attribute converts from the identifier or
+//! version it wraps with `From`.
 use core::str::FromStr;
-use std::string::String;
 
 use derive_more::From;
 use ibc_core_connection_types::error::ConnectionError;
 use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId};
+use ibc_primitives::prelude::*;
 use tendermint::abci;
 
+use super::attribute_map::{AttributeKey, AttributeMap};
+use super::{
+    attribute_value, keys, parse_attribute_value, record_parse_error, trimmed_attribute_value,
+    OpenAck, OpenConfirm, OpenInit, OpenTry,
+};
 use crate::error::ChannelError;
 use crate::Version;
-const CONNECTION_ID_ATTRIBUTE_KEY: &str = "connection_id";
-const CHANNEL_ID_ATTRIBUTE_KEY: &str = "channel_id";
-const PORT_ID_ATTRIBUTE_KEY: &str = "port_id";
+
+pub(super) const CONNECTION_ID_ATTRIBUTE_KEY: &str = keys::CONNECTION_ID;
+pub(super) const CHANNEL_ID_ATTRIBUTE_KEY: &str = keys::CHANNEL_ID;
+const PORT_ID_ATTRIBUTE_KEY: &str = keys::PORT_ID;
 /// This attribute key is public so that OpenInit can use it to convert itself
 /// to an `AbciEvent`
-pub(super) const COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY: &str = "counterparty_channel_id";
-const COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY: &str = "counterparty_port_id";
-const VERSION_ATTRIBUTE_KEY: &str = "version";
+pub(super) const COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY: &str = keys::COUNTERPARTY_CHANNEL_ID;
+const COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY: &str = keys::COUNTERPARTY_PORT_ID;
+const VERSION_ATTRIBUTE_KEY: &str = keys::VERSION;
+const MODULE_ATTRIBUTE_KEY: &str = keys::MODULE;
 
 #[cfg_attr(
     feature = "parity-scale-codec",
@@ -35,39 +44,38 @@ const VERSION_ATTRIBUTE_KEY: &str = "version";
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct PortIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "portId"))]
     pub port_id: PortId,
 }
 
-impl From<PortIdAttribute> for abci::EventAttribute {
-    fn from(attr: PortIdAttribute) -> Self {
+impl From<&PortIdAttribute> for abci::EventAttribute {
+    fn from(attr: &PortIdAttribute) -> Self {
         (PORT_ID_ATTRIBUTE_KEY, attr.port_id.as_str()).into()
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for PortIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        let value = trimmed_attribute_value(value, PORT_ID_ATTRIBUTE_KEY)?;
+        let port_id = PortId::from_str(value).map_err(|e| record_parse_error(e.into()))?;
+
+        Ok(PortIdAttribute { port_id })
+    }
+}
+
 impl TryFrom<abci::EventAttribute> for PortIdAttribute {
     type Error = ChannelError;
+
     fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
-        if let Ok(key_str) = value.key_str() {
-            if key_str != PORT_ID_ATTRIBUTE_KEY {
-                return Err(ChannelError::InvalidAttributeKey {
-                    attribute_key: key_str.to_string(),
-                });
-            }
-        } else {
-            return Err(ChannelError::InvalidAttributeKey {
-                attribute_key: String::new(),
-            });
-        }
-        value
-            .value_str()
-            .map(|value| {
-                let port_id = PortId::from_str(value)?;
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
 
-                Ok(PortIdAttribute { port_id })
-            })
-            .map_err(|_| ChannelError::InvalidAttributeValue {
-                attribute_value: String::new(),
-            })?
+impl AsRef<str> for PortIdAttribute {
+    fn as_ref(&self) -> &str {
+        self.port_id.as_str()
     }
 }
 
@@ -86,44 +94,37 @@ impl TryFrom<abci::EventAttribute> for PortIdAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct ChannelIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "channelId"))]
     pub channel_id: ChannelId,
 }
 
-impl From<ChannelIdAttribute> for abci::EventAttribute {
-    fn from(attr: ChannelIdAttribute) -> Self {
+impl From<&ChannelIdAttribute> for abci::EventAttribute {
+    fn from(attr: &ChannelIdAttribute) -> Self {
         (CHANNEL_ID_ATTRIBUTE_KEY, attr.channel_id.as_str()).into()
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for ChannelIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(ChannelIdAttribute {
+            channel_id: parse_attribute_value(value, CHANNEL_ID_ATTRIBUTE_KEY)?,
+        })
+    }
+}
+
 impl TryFrom<abci::EventAttribute> for ChannelIdAttribute {
     type Error = ChannelError;
-    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
-        if let Ok(key_str) = value.key_str() {
-            if key_str != CHANNEL_ID_ATTRIBUTE_KEY {
-                return Err(ChannelError::InvalidAttributeKey {
-                    attribute_key: key_str.to_string(),
-                });
-            }
-        } else {
-            return Err(ChannelError::InvalidAttributeKey {
-                attribute_key: String::new(),
-            });
-        }
 
-        value
-            .value_str()
-            .map(|value| {
-                let channel_id = ChannelId::from_str(value).map_err(|_| {
-                    ChannelError::InvalidAttributeValue {
-                        attribute_value: value.to_string(),
-                    }
-                })?;
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
 
-                Ok(ChannelIdAttribute { channel_id })
-            })
-            .map_err(|_| ChannelError::InvalidAttributeValue {
-                attribute_value: String::new(),
-            })?
+impl AsRef<str> for ChannelIdAttribute {
+    fn as_ref(&self) -> &str {
+        self.channel_id.as_str()
     }
 }
 #[cfg_attr(
@@ -141,11 +142,12 @@ impl TryFrom<abci::EventAttribute> for ChannelIdAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct CounterpartyPortIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "counterpartyPortId"))]
     pub counterparty_port_id: PortId,
 }
 
-impl From<CounterpartyPortIdAttribute> for abci::EventAttribute {
-    fn from(attr: CounterpartyPortIdAttribute) -> Self {
+impl From<&CounterpartyPortIdAttribute> for abci::EventAttribute {
+    fn from(attr: &CounterpartyPortIdAttribute) -> Self {
         (
             COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY,
             attr.counterparty_port_id.as_str(),
@@ -154,37 +156,27 @@ impl From<CounterpartyPortIdAttribute> for abci::EventAttribute {
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for CounterpartyPortIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(CounterpartyPortIdAttribute {
+            counterparty_port_id: parse_attribute_value(value, COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY)?,
+        })
+    }
+}
+
 impl TryFrom<abci::EventAttribute> for CounterpartyPortIdAttribute {
     type Error = ChannelError;
 
     fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
-        if let Ok(key_str) = value.key_str() {
-            if key_str != COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY {
-                return Err(ChannelError::InvalidAttributeKey {
-                    attribute_key: key_str.to_string(),
-                });
-            }
-        } else {
-            return Err(ChannelError::InvalidAttributeKey {
-                attribute_key: String::new(),
-            });
-        }
-
-        value
-            .value_str()
-            .map(|value| {
-                let counterparty_port_id =
-                    PortId::from_str(value).map_err(|_| ChannelError::InvalidAttributeValue {
-                        attribute_value: value.to_string(),
-                    })?;
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
 
-                Ok(CounterpartyPortIdAttribute {
-                    counterparty_port_id,
-                })
-            })
-            .map_err(|_| ChannelError::InvalidAttributeValue {
-                attribute_value: String::new(),
-            })?
+impl AsRef<str> for CounterpartyPortIdAttribute {
+    fn as_ref(&self) -> &str {
+        self.counterparty_port_id.as_str()
     }
 }
 #[cfg_attr(
@@ -202,11 +194,12 @@ impl TryFrom<abci::EventAttribute> for CounterpartyPortIdAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct CounterpartyChannelIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "counterpartyChannelId"))]
     pub counterparty_channel_id: ChannelId,
 }
 
-impl From<CounterpartyChannelIdAttribute> for abci::EventAttribute {
-    fn from(attr: CounterpartyChannelIdAttribute) -> Self {
+impl From<&CounterpartyChannelIdAttribute> for abci::EventAttribute {
+    fn from(attr: &CounterpartyChannelIdAttribute) -> Self {
         (
             COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY,
             attr.counterparty_channel_id.as_str(),
@@ -215,38 +208,24 @@ impl From<CounterpartyChannelIdAttribute> for abci::EventAttribute {
     }
 }
 
-impl TryFrom<abci::EventAttribute> for CounterpartyChannelIdAttribute {
+impl TryFrom<(&[u8], &[u8])> for CounterpartyChannelIdAttribute {
     type Error = ChannelError;
 
-    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
-        if let Ok(key_str) = value.key_str() {
-            if key_str != COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY {
-                return Err(ChannelError::InvalidAttributeKey {
-                    attribute_key: key_str.to_string(),
-                });
-            }
-        } else {
-            return Err(ChannelError::InvalidAttributeKey {
-                attribute_key: String::new(),
-            });
-        }
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(CounterpartyChannelIdAttribute {
+            counterparty_channel_id: parse_attribute_value(
+                value,
+                COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY,
+            )?,
+        })
+    }
+}
 
-        value
-            .value_str()
-            .map(|value| {
-                let counterparty_channel_id = ChannelId::from_str(value).map_err(|_| {
-                    ChannelError::InvalidAttributeValue {
-                        attribute_value: value.to_string(),
-                    }
-                })?;
+impl TryFrom<abci::EventAttribute> for CounterpartyChannelIdAttribute {
+    type Error = ChannelError;
 
-                Ok(CounterpartyChannelIdAttribute {
-                    counterparty_channel_id,
-                })
-            })
-            .map_err(|_| ChannelError::InvalidAttributeValue {
-                attribute_value: String::new(),
-            })?
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
     }
 }
 
@@ -271,15 +250,34 @@ impl AsRef<ChannelId> for CounterpartyChannelIdAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct ConnectionIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "connectionId"))]
     pub connection_id: ConnectionId,
 }
 
-impl From<ConnectionIdAttribute> for abci::EventAttribute {
-    fn from(attr: ConnectionIdAttribute) -> Self {
+impl From<&ConnectionIdAttribute> for abci::EventAttribute {
+    fn from(attr: &ConnectionIdAttribute) -> Self {
         (CONNECTION_ID_ATTRIBUTE_KEY, attr.connection_id.as_str()).into()
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for ConnectionIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(ConnectionIdAttribute {
+            connection_id: parse_attribute_value(value, CONNECTION_ID_ATTRIBUTE_KEY)?,
+        })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for ConnectionIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -304,34 +302,237 @@ impl From<VersionAttribute> for abci::EventAttribute {
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for VersionAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(VersionAttribute {
+            version: Version::new(attribute_value(value, VERSION_ATTRIBUTE_KEY)?.to_string()),
+        })
+    }
+}
+
 impl TryFrom<abci::EventAttribute> for VersionAttribute {
     type Error = ChannelError;
 
     fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
-        if let Ok(key_str) = value.key_str() {
-            if key_str != VERSION_ATTRIBUTE_KEY {
-                return Err(ChannelError::InvalidAttributeKey {
-                    attribute_key: key_str.to_string(),
-                });
-            }
-        } else {
-            return Err(ChannelError::InvalidAttributeKey {
-                attribute_key: String::new(),
-            });
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
+/// The name of the module emitting an event, e.g. `transfer`, which chains
+/// attach to IBC events as the `module` attribute.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, From, PartialEq, Eq)]
+pub struct ModuleAttribute {
+    pub module: String,
+}
+
+impl From<ModuleAttribute> for abci::EventAttribute {
+    fn from(attr: ModuleAttribute) -> Self {
+        (MODULE_ATTRIBUTE_KEY, attr.module).into()
+    }
+}
+
+impl TryFrom<(&[u8], &[u8])> for ModuleAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(ModuleAttribute {
+            module: trimmed_attribute_value(value, MODULE_ATTRIBUTE_KEY)?.to_string(),
+        })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for ModuleAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
+impl_borrowed_emission!(
+    PortIdAttribute,
+    ChannelIdAttribute,
+    CounterpartyPortIdAttribute,
+    CounterpartyChannelIdAttribute,
+    ConnectionIdAttribute,
+);
+
+impl_validate_by_reparsing!(
+    PortIdAttribute,
+    ChannelIdAttribute,
+    CounterpartyPortIdAttribute,
+    CounterpartyChannelIdAttribute,
+    ConnectionIdAttribute,
+    VersionAttribute,
+);
+
+impl_attribute_key!(
+    PortIdAttribute => PORT_ID_ATTRIBUTE_KEY,
+    ChannelIdAttribute => CHANNEL_ID_ATTRIBUTE_KEY,
+    CounterpartyPortIdAttribute => COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY,
+    ConnectionIdAttribute => CONNECTION_ID_ATTRIBUTE_KEY,
+    ModuleAttribute => MODULE_ATTRIBUTE_KEY,
+    VersionAttribute => VERSION_ATTRIBUTE_KEY,
+);
+
+impl AttributeKey for CounterpartyChannelIdAttribute {
+    const KEY: &'static str = COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY;
+    // `OpenInit` emits an empty counterparty channel identifier, as it is not
+    // known yet.
+    const EMPTY_IS_ABSENT: bool = true;
+}
+
+/// The local end of a channel as described by a handshake event, i.e. the
+/// keys needed to look up its connection and channel in the stores.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelEndpoint {
+    pub connection_id: ConnectionId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+}
+
+impl ChannelEndpoint {
+    /// Parses the attributes of the event without taking ownership of them.
+    pub fn try_from_slice(attributes: &[abci::EventAttribute]) -> Result<Self, ChannelError> {
+        let attrs = AttributeMap::from(attributes);
+
+        Ok(ChannelEndpoint {
+            connection_id: attrs.get::<ConnectionIdAttribute>()?.connection_id,
+            port_id: attrs.get::<PortIdAttribute>()?.port_id,
+            channel_id: attrs.get::<ChannelIdAttribute>()?.channel_id,
+        })
+    }
+}
+
+impl TryFrom<Vec<abci::EventAttribute>> for ChannelEndpoint {
+    type Error = ChannelError;
+
+    fn try_from(attributes: Vec<abci::EventAttribute>) -> Result<Self, Self::Error> {
+        Self::try_from_slice(&attributes)
+    }
+}
+
+impl OpenInit {
+    /// Parses the attributes of the event without taking ownership of them.
+    ///
+    /// The counterparty channel identifier is only known from `OpenTry` on,
+    /// so a non-empty `counterparty_channel_id` attribute is rejected with
+    /// [`ChannelError::UnexpectedCounterpartyChannelId`].
+    pub fn try_from_slice(attributes: &[abci::EventAttribute]) -> Result<Self, ChannelError> {
+        let attrs = AttributeMap::from(attributes);
+
+        if let Some(attribute) = attrs.get_raw::<CounterpartyChannelIdAttribute>()? {
+            return Err(record_parse_error(
+                ChannelError::UnexpectedCounterpartyChannelId {
+                    counterparty_channel_id: String::from_utf8_lossy(attribute.value_bytes())
+                        .into_owned(),
+                },
+            ));
         }
 
-        value
-            .value_str()
-            .map(|value| {
-                let version =
-                    Version::from_str(value).map_err(|_| ChannelError::InvalidAttributeValue {
-                        attribute_value: value.to_string(),
-                    })?;
-
-                Ok(VersionAttribute { version })
-            })
-            .map_err(|_| ChannelError::InvalidAttributeValue {
-                attribute_value: String::new(),
-            })?
+        Ok(OpenInit {
+            port_id_attr_on_a: attrs.get::<PortIdAttribute>()?,
+            chan_id_attr_on_a: attrs.get::<ChannelIdAttribute>()?,
+            port_id_attr_on_b: attrs.get::<CounterpartyPortIdAttribute>()?,
+            conn_id_attr_on_a: attrs.get::<ConnectionIdAttribute>()?,
+            version_attr_on_a: attrs.get::<VersionAttribute>()?,
+            module_attr: attrs.get_optional::<ModuleAttribute>()?,
+        })
+    }
+}
+
+impl TryFrom<Vec<abci::EventAttribute>> for OpenInit {
+    type Error = ChannelError;
+
+    fn try_from(attributes: Vec<abci::EventAttribute>) -> Result<Self, Self::Error> {
+        Self::try_from_slice(&attributes)
+    }
+}
+
+impl OpenTry {
+    /// Parses the attributes of the event without taking ownership of them.
+    pub fn try_from_slice(attributes: &[abci::EventAttribute]) -> Result<Self, ChannelError> {
+        let attrs = AttributeMap::from(attributes);
+
+        Ok(OpenTry {
+            port_id_attr_on_b: attrs.get::<PortIdAttribute>()?,
+            chan_id_attr_on_b: attrs.get::<ChannelIdAttribute>()?,
+            port_id_attr_on_a: attrs.get::<CounterpartyPortIdAttribute>()?,
+            chan_id_attr_on_a: attrs.get::<CounterpartyChannelIdAttribute>()?,
+            conn_id_attr_on_b: attrs.get::<ConnectionIdAttribute>()?,
+            version_attr_on_b: attrs.get::<VersionAttribute>()?,
+            module_attr: attrs.get_optional::<ModuleAttribute>()?,
+        })
+    }
+}
+
+impl TryFrom<Vec<abci::EventAttribute>> for OpenTry {
+    type Error = ChannelError;
+
+    fn try_from(attributes: Vec<abci::EventAttribute>) -> Result<Self, Self::Error> {
+        Self::try_from_slice(&attributes)
+    }
+}
+
+impl OpenAck {
+    /// Parses the attributes of the event without taking ownership of them.
+    pub fn try_from_slice(attributes: &[abci::EventAttribute]) -> Result<Self, ChannelError> {
+        let attrs = AttributeMap::from(attributes);
+
+        Ok(OpenAck {
+            port_id_attr_on_a: attrs.get::<PortIdAttribute>()?,
+            chan_id_attr_on_a: attrs.get::<ChannelIdAttribute>()?,
+            port_id_attr_on_b: attrs.get::<CounterpartyPortIdAttribute>()?,
+            chan_id_attr_on_b: attrs.get::<CounterpartyChannelIdAttribute>()?,
+            conn_id_attr_on_a: attrs.get::<ConnectionIdAttribute>()?,
+            module_attr: attrs.get_optional::<ModuleAttribute>()?,
+        })
+    }
+}
+
+impl TryFrom<Vec<abci::EventAttribute>> for OpenAck {
+    type Error = ChannelError;
+
+    fn try_from(attributes: Vec<abci::EventAttribute>) -> Result<Self, Self::Error> {
+        Self::try_from_slice(&attributes)
+    }
+}
+
+impl OpenConfirm {
+    /// Parses the attributes of the event without taking ownership of them.
+    pub fn try_from_slice(attributes: &[abci::EventAttribute]) -> Result<Self, ChannelError> {
+        let attrs = AttributeMap::from(attributes);
+
+        Ok(OpenConfirm {
+            port_id_attr_on_b: attrs.get::<PortIdAttribute>()?,
+            chan_id_attr_on_b: attrs.get::<ChannelIdAttribute>()?,
+            port_id_attr_on_a: attrs.get::<CounterpartyPortIdAttribute>()?,
+            chan_id_attr_on_a: attrs.get::<CounterpartyChannelIdAttribute>()?,
+            conn_id_attr_on_b: attrs.get::<ConnectionIdAttribute>()?,
+            module_attr: attrs.get_optional::<ModuleAttribute>()?,
+        })
+    }
+}
+
+impl TryFrom<Vec<abci::EventAttribute>> for OpenConfirm {
+    type Error = ChannelError;
+
+    fn try_from(attributes: Vec<abci::EventAttribute>) -> Result<Self, Self::Error> {
+        Self::try_from_slice(&attributes)
     }
 }
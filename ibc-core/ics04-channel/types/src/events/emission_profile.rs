@@ -0,0 +1,288 @@
+//! Emission of channel events in the attribute layout of a given ibc-go
+//! release, for chains that need byte-identical events.
+use ibc_primitives::prelude::*;
+use tendermint::abci;
+
+use super::channel_attributes::{ConnectionIdAttribute, CONNECTION_ID_ATTRIBUTE_KEY};
+use super::packet_attributes::{
+    PacketConnectionIdAttribute, PKT_ACK_ATTRIBUTE_KEY, PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+    PKT_DATA_ATTRIBUTE_KEY,
+};
+
+/// The attribute layout channel events are emitted with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmissionProfile {
+    /// The layout emitted by the event conversions of this crate.
+    #[default]
+    Default,
+    /// The layout of ibc-go v10, which drops the deprecated plaintext
+    /// `packet_data` and `packet_ack` attributes, and follows the deprecated
+    /// `packet_connection` attribute with its `connection_id` replacement.
+    ///
+    /// Handshake events are unchanged in ibc-go v10.
+    IbcGoV10,
+}
+
+impl EmissionProfile {
+    /// Tells whether the deprecated plaintext `packet_data` and `packet_ack`
+    /// attributes are emitted alongside their hex-encoded counterparts.
+    pub const fn emits_plaintext(self) -> bool {
+        matches!(self, Self::Default)
+    }
+
+    /// The `connection_id` attribute following the `packet_connection` one of
+    /// packet events in the layout of this profile, if any.
+    pub(super) fn connection_id_attribute(
+        self,
+        attr: &PacketConnectionIdAttribute,
+    ) -> Option<abci::EventAttribute> {
+        match self {
+            Self::Default => None,
+            Self::IbcGoV10 => Some(ConnectionIdAttribute::from(attr.connection_id.clone()).into()),
+        }
+    }
+
+    /// Rewrites an event emitted with the [`EmissionProfile::Default`] layout
+    /// into the layout of this profile. Attributes unknown to the profile are
+    /// kept in place.
+    ///
+    /// Emitting the event straight into the layout, e.g. with
+    /// [`SendPacket::to_abci_event`](super::SendPacket::to_abci_event), is
+    /// preferred: the default layout requires packet data and
+    /// acknowledgements to be valid UTF-8, which the ibc-go v10 one does not.
+    pub fn apply(self, event: abci::Event) -> abci::Event {
+        match self {
+            Self::Default => event,
+            Self::IbcGoV10 => {
+                let mut attributes = Vec::with_capacity(event.attributes.len() + 1);
+
+                for attribute in event.attributes {
+                    match attribute.key_bytes() {
+                        key if key == PKT_DATA_ATTRIBUTE_KEY.as_bytes()
+                            || key == PKT_ACK_ATTRIBUTE_KEY.as_bytes() => {}
+                        key if key == PKT_CONNECTION_ID_ATTRIBUTE_KEY.as_bytes() => {
+                            let connection_id: Option<abci::EventAttribute> = attribute
+                                .value_str()
+                                .ok()
+                                .map(|value| (CONNECTION_ID_ATTRIBUTE_KEY, value).into());
+                            attributes.push(attribute);
+                            attributes.extend(connection_id);
+                        }
+                        _ => attributes.push(attribute),
+                    }
+                }
+
+                abci::Event {
+                    kind: event.kind,
+                    attributes,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId};
+
+    use super::*;
+    use crate::acknowledgement::Acknowledgement;
+    use crate::channel::Order;
+    use crate::events::packet_attributes::{AcknowledgementAttribute, PacketDataAttribute};
+    use crate::events::{mock_packet, OpenInit, SendPacket, WriteAcknowledgement};
+    use crate::packet::Packet;
+    use crate::Version;
+
+    /// The attributes of a captured event, none of which is flagged as indexed
+    /// by ibc-go.
+    fn captured_attributes(key_values: &[(&str, &str)]) -> Vec<abci::EventAttribute> {
+        key_values
+            .iter()
+            .map(|&(key, value)| (key, value, false).into())
+            .collect()
+    }
+
+    fn ics20_packet(data: Vec<u8>) -> Packet {
+        Packet {
+            data,
+            ..mock_packet(1)
+        }
+    }
+
+    #[test]
+    fn send_packet_matches_ibc_go_v10() {
+        // Captured from an ibc-go v10 `send_packet` event of an ICS-20 transfer.
+        let ibc_go_v10_attributes = captured_attributes(&[
+            ("packet_data_hex", "7b22616d6f756e74223a22313030227d"),
+            ("packet_timeout_height", "0-0"),
+            ("packet_timeout_timestamp", "1000"),
+            ("packet_sequence", "1"),
+            ("packet_src_port", "transfer"),
+            ("packet_src_channel", "channel-0"),
+            ("packet_dst_port", "transfer"),
+            ("packet_dst_channel", "channel-1"),
+            ("packet_channel_ordering", "ORDER_UNORDERED"),
+            ("packet_connection", "connection-0"),
+            ("connection_id", "connection-0"),
+        ]);
+
+        let send_packet = SendPacket::new(
+            ics20_packet(b"{\"amount\":\"100\"}".to_vec()),
+            Order::Unordered,
+            ConnectionId::zero(),
+        );
+        let event: abci::Event = send_packet.clone().try_into().unwrap();
+        assert_eq!(
+            send_packet
+                .clone()
+                .to_abci_event(EmissionProfile::Default)
+                .unwrap(),
+            event
+        );
+        assert_eq!(EmissionProfile::Default.apply(event.clone()), event);
+
+        let v10_event = send_packet
+            .to_abci_event(EmissionProfile::IbcGoV10)
+            .unwrap();
+        assert_eq!(v10_event.kind, "send_packet");
+        assert_eq!(v10_event.attributes, ibc_go_v10_attributes);
+        assert_eq!(EmissionProfile::IbcGoV10.apply(event), v10_event);
+    }
+
+    #[test]
+    fn send_binary_packet_with_ibc_go_v10_profile() {
+        let send_packet = SendPacket::new(
+            ics20_packet(vec![0xff, 0xfe]),
+            Order::Unordered,
+            ConnectionId::zero(),
+        );
+        assert!(abci::Event::try_from(send_packet.clone()).is_err());
+
+        let v10_event = send_packet
+            .to_abci_event(EmissionProfile::IbcGoV10)
+            .unwrap();
+        assert_eq!(
+            v10_event.attributes[0],
+            abci::EventAttribute::from(("packet_data_hex", "fffe", false))
+        );
+        assert_eq!(
+            SendPacket::try_from(v10_event).unwrap().packet_data(),
+            [0xff, 0xfe]
+        );
+    }
+
+    #[test]
+    fn open_init_matches_ibc_go_v10() {
+        // Captured from an ibc-go v10 `channel_open_init` event.
+        let ibc_go_v10_attributes = captured_attributes(&[
+            ("port_id", "transfer"),
+            ("channel_id", "channel-0"),
+            ("counterparty_port_id", "transfer"),
+            ("counterparty_channel_id", ""),
+            ("connection_id", "connection-0"),
+            ("version", "ics20-1"),
+        ]);
+
+        let event: abci::Event = OpenInit::new(
+            PortId::transfer(),
+            ChannelId::zero(),
+            PortId::transfer(),
+            ConnectionId::zero(),
+            Version::new("ics20-1".to_string()),
+        )
+        .into();
+
+        let v10_event = EmissionProfile::IbcGoV10.apply(event);
+        assert_eq!(v10_event.kind, "channel_open_init");
+        assert_eq!(v10_event.attributes, ibc_go_v10_attributes);
+    }
+
+    #[test]
+    fn data_and_ack_attributes_follow_the_same_profile() {
+        let keys = |attributes: Vec<abci::EventAttribute>| -> Vec<String> {
+            attributes
+                .iter()
+                .map(|attr| attr.key_str().unwrap().to_string())
+                .collect()
+        };
+        let packet_data = PacketDataAttribute {
+            packet_data: b"data".to_vec(),
+        };
+        let acknowledgement = AcknowledgementAttribute {
+            acknowledgement: Acknowledgement::try_from(b"ack".to_vec()).unwrap(),
+        };
+
+        let profile = EmissionProfile::Default;
+        assert_eq!(
+            keys(packet_data.clone().into_attributes(profile).unwrap()),
+            ["packet_data", "packet_data_hex"]
+        );
+        assert_eq!(
+            keys(acknowledgement.clone().into_attributes(profile).unwrap()),
+            ["packet_ack", "packet_ack_hex"]
+        );
+
+        let profile = EmissionProfile::IbcGoV10;
+        assert_eq!(
+            keys(packet_data.into_attributes(profile).unwrap()),
+            ["packet_data_hex"]
+        );
+        assert_eq!(
+            keys(acknowledgement.into_attributes(profile).unwrap()),
+            ["packet_ack_hex"]
+        );
+
+        // Without plaintext attributes, non UTF-8 payloads can be emitted.
+        let non_utf8 = PacketDataAttribute {
+            packet_data: vec![0xff, 0xfe],
+        };
+        assert!(non_utf8
+            .clone()
+            .into_attributes(EmissionProfile::Default)
+            .is_err());
+        assert_eq!(
+            non_utf8.into_attributes(profile).unwrap(),
+            vec![abci::EventAttribute::from(("packet_data_hex", "fffe"))]
+        );
+    }
+
+    #[test]
+    fn write_ack_follows_the_profile_of_the_chain() {
+        let write_ack = WriteAcknowledgement::new(
+            ics20_packet(vec![0xff]),
+            Acknowledgement::try_from(vec![0xfe]).unwrap(),
+            ConnectionId::zero(),
+        );
+        assert!(abci::Event::try_from(write_ack.clone()).is_err());
+
+        let v10_event = write_ack
+            .clone()
+            .to_abci_event(EmissionProfile::IbcGoV10)
+            .unwrap();
+        let keys: Vec<&str> = v10_event
+            .attributes
+            .iter()
+            .map(|attr| attr.key_str().unwrap())
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "packet_data_hex",
+                "packet_timeout_height",
+                "packet_timeout_timestamp",
+                "packet_sequence",
+                "packet_src_port",
+                "packet_src_channel",
+                "packet_dst_port",
+                "packet_dst_channel",
+                "packet_ack_hex",
+                "packet_connection",
+                "connection_id",
+            ]
+        );
+        assert_eq!(
+            WriteAcknowledgement::try_from(v10_event).unwrap(),
+            write_ack
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Extraction of the attributes of an event straight into caller-owned
+//! structs, for high-throughput ingestion that has no use for the event types.
+use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId, Sequence};
+use ibc_primitives::prelude::*;
+use ibc_primitives::Timestamp;
+use tendermint::abci;
+
+use super::packet_attributes::{
+    packet_event_attribute_key, ChannelOrderingAttribute, DstChannelIdAttribute,
+    DstPortIdAttribute, PacketConnectionIdAttribute, PacketDataAttribute, SequenceAttribute,
+    SrcChannelIdAttribute, SrcPortIdAttribute, TimeoutHeightAttribute, TimeoutTimestampAttribute,
+    PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY, PKT_CONNECTION_ID_ATTRIBUTE_KEY, PKT_DATA_ATTRIBUTE_KEY,
+    PKT_DATA_HEX_ATTRIBUTE_KEY, PKT_DST_CHANNEL_ATTRIBUTE_KEY, PKT_DST_PORT_ATTRIBUTE_KEY,
+    PKT_SEQ_ATTRIBUTE_KEY, PKT_SRC_CHANNEL_ATTRIBUTE_KEY, PKT_SRC_PORT_ATTRIBUTE_KEY,
+    PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY, PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+};
+use super::{canonical_event_kind, missing_attribute_key, record_parse_error, SEND_PACKET_EVENT};
+use crate::channel::Order;
+use crate::error::ChannelError;
+use crate::timeout::TimeoutHeight;
+
+/// Populates a caller-owned struct from the attributes of an event, e.g.
+/// `event.extract_into(&mut my_struct)`.
+pub trait ExtractInto<T: ?Sized> {
+    fn extract_into(&self, target: &mut T) -> Result<(), ChannelError>;
+}
+
+/// A caller-owned struct populated from the attributes of a `send_packet`
+/// event. Every setter ignores its value by default, so that only the fields
+/// of interest need to be implemented.
+pub trait SendPacketFields {
+    /// Receives the packet data, decoded from `packet_data_hex`, or borrowed
+    /// from the deprecated `packet_data` attribute if the event has no hex
+    /// attribute.
+    fn set_packet_data(&mut self, _packet_data: &[u8]) {}
+    fn set_timeout_height_on_b(&mut self, _timeout_height_on_b: TimeoutHeight) {}
+    fn set_timeout_timestamp_on_b(&mut self, _timeout_timestamp_on_b: Timestamp) {}
+    fn set_seq_on_a(&mut self, _seq_on_a: Sequence) {}
+    fn set_port_id_on_a(&mut self, _port_id_on_a: PortId) {}
+    fn set_chan_id_on_a(&mut self, _chan_id_on_a: ChannelId) {}
+    fn set_port_id_on_b(&mut self, _port_id_on_b: PortId) {}
+    fn set_chan_id_on_b(&mut self, _chan_id_on_b: ChannelId) {}
+    fn set_channel_ordering(&mut self, _channel_ordering: Order) {}
+    fn set_conn_id_on_a(&mut self, _conn_id_on_a: ConnectionId) {}
+}
+
+/// The attributes a `send_packet` event must carry, in the order in which
+/// missing ones are reported.
+const REQUIRED_SEND_PACKET_KEYS: [&str; 10] = [
+    PKT_DATA_HEX_ATTRIBUTE_KEY,
+    PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY,
+    PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+    PKT_SEQ_ATTRIBUTE_KEY,
+    PKT_SRC_PORT_ATTRIBUTE_KEY,
+    PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+    PKT_DST_PORT_ATTRIBUTE_KEY,
+    PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+    PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+    PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+];
+
+/// Parses the attributes of a `send_packet` event one by one, without
+/// building a `SendPacket`, and hands each value to the matching setter. Only
+/// the hex-encoded packet data is decoded into a new buffer.
+///
+/// Keys are matched like in `SendPacket::try_from`. Fails on events of another
+/// kind, on malformed attributes, with [`ChannelError::DuplicateAttributeKey`]
+/// on a repeated standard attribute and, with
+/// [`ChannelError::MissingAttributeKey`], on events missing a required
+/// attribute, once the setters of the others have been called. Non-standard
+/// attributes are skipped.
+impl<T: SendPacketFields + ?Sized> ExtractInto<T> for abci::Event {
+    fn extract_into(&self, target: &mut T) -> Result<(), ChannelError> {
+        if canonical_event_kind(&self.kind) != SEND_PACKET_EVENT {
+            return Err(ChannelError::Other {
+                description: format!("Error in parsing `{SEND_PACKET_EVENT}` event"),
+            });
+        }
+
+        let standard_key = |attribute: &abci::EventAttribute| {
+            attribute
+                .key_str()
+                .ok()
+                .and_then(packet_event_attribute_key)
+        };
+        let has_hex_data = self
+            .attributes
+            .iter()
+            .any(|attribute| standard_key(attribute) == Some(PKT_DATA_HEX_ATTRIBUTE_KEY));
+
+        let required_bit = |key: &str| {
+            REQUIRED_SEND_PACKET_KEYS
+                .iter()
+                .position(|required| *required == key)
+                .map(|index| 1u16 << index)
+        };
+
+        let mut found = 0u16;
+        let mut found_plaintext_data = false;
+        for attribute in &self.attributes {
+            let value = (attribute.key_bytes(), attribute.value_bytes());
+            let Some(key) = standard_key(attribute) else {
+                continue;
+            };
+
+            let repeated = match key {
+                PKT_DATA_ATTRIBUTE_KEY => core::mem::replace(&mut found_plaintext_data, true),
+                key => required_bit(key).is_some_and(|bit| {
+                    let repeated = found & bit != 0;
+                    found |= bit;
+                    repeated
+                }),
+            };
+            if repeated {
+                return Err(record_parse_error(ChannelError::DuplicateAttributeKey {
+                    attribute_key: key.to_string(),
+                }));
+            }
+
+            match key {
+                PKT_DATA_HEX_ATTRIBUTE_KEY => {
+                    let attr = PacketDataAttribute::try_from(value)?;
+                    target.set_packet_data(&attr.packet_data);
+                }
+                PKT_DATA_ATTRIBUTE_KEY if !has_hex_data => {
+                    target.set_packet_data(attribute.value_bytes());
+                }
+                PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY => target.set_timeout_height_on_b(
+                    TimeoutHeightAttribute::try_from(value)?.timeout_height,
+                ),
+                PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY => target.set_timeout_timestamp_on_b(
+                    TimeoutTimestampAttribute::try_from(value)?.timeout_timestamp,
+                ),
+                PKT_SEQ_ATTRIBUTE_KEY => {
+                    target.set_seq_on_a(SequenceAttribute::try_from(value)?.sequence)
+                }
+                PKT_SRC_PORT_ATTRIBUTE_KEY => {
+                    target.set_port_id_on_a(SrcPortIdAttribute::try_from(value)?.src_port_id)
+                }
+                PKT_SRC_CHANNEL_ATTRIBUTE_KEY => {
+                    target.set_chan_id_on_a(SrcChannelIdAttribute::try_from(value)?.src_channel_id)
+                }
+                PKT_DST_PORT_ATTRIBUTE_KEY => {
+                    target.set_port_id_on_b(DstPortIdAttribute::try_from(value)?.dst_port_id)
+                }
+                PKT_DST_CHANNEL_ATTRIBUTE_KEY => {
+                    target.set_chan_id_on_b(DstChannelIdAttribute::try_from(value)?.dst_channel_id)
+                }
+                PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY => {
+                    target.set_channel_ordering(ChannelOrderingAttribute::try_from(value)?.order)
+                }
+                PKT_CONNECTION_ID_ATTRIBUTE_KEY => target
+                    .set_conn_id_on_a(PacketConnectionIdAttribute::try_from(value)?.connection_id),
+                _ => {}
+            }
+        }
+
+        // The plaintext packet data stands in for the hex-encoded one.
+        if found_plaintext_data {
+            found |= required_bit(PKT_DATA_HEX_ATTRIBUTE_KEY).unwrap_or_default();
+        }
+
+        match REQUIRED_SEND_PACKET_KEYS
+            .iter()
+            .enumerate()
+            .find(|(index, _)| found & (1 << index) == 0)
+        {
+            Some((_, key)) => Err(missing_attribute_key(key)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{mock_packet, SendPacket};
+
+    /// An ingestion record keeping only the fields it indexes packets by.
+    #[derive(Debug, Default)]
+    struct PacketRecord {
+        sequence: u64,
+        src_channel: Option<ChannelId>,
+        data_len: usize,
+    }
+
+    impl SendPacketFields for PacketRecord {
+        fn set_packet_data(&mut self, packet_data: &[u8]) {
+            self.data_len = packet_data.len();
+        }
+
+        fn set_seq_on_a(&mut self, seq_on_a: Sequence) {
+            self.sequence = seq_on_a.value();
+        }
+
+        fn set_chan_id_on_a(&mut self, chan_id_on_a: ChannelId) {
+            self.src_channel = Some(chan_id_on_a);
+        }
+    }
+
+    fn send_packet_event() -> abci::Event {
+        let mut packet = mock_packet(7);
+        packet.chan_id_on_a = ChannelId::new(3);
+
+        SendPacket::new(packet, Order::Unordered, ConnectionId::zero())
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn extract_send_packet_into_record() {
+        let mut record = PacketRecord::default();
+        send_packet_event().extract_into(&mut record).unwrap();
+
+        assert_eq!(record.sequence, 7);
+        assert_eq!(record.src_channel, Some(ChannelId::new(3)));
+        assert_eq!(record.data_len, 16);
+    }
+
+    #[test]
+    fn extract_rejects_malformed_events() {
+        let mut malformed = send_packet_event();
+        for attribute in &mut malformed.attributes {
+            if attribute.key_bytes() == PKT_SEQ_ATTRIBUTE_KEY.as_bytes() {
+                *attribute = (PKT_SEQ_ATTRIBUTE_KEY, "seven").into();
+            }
+        }
+        assert!(malformed
+            .extract_into(&mut PacketRecord::default())
+            .is_err());
+
+        let mut incomplete = send_packet_event();
+        incomplete
+            .attributes
+            .retain(|attribute| attribute.key_bytes() != PKT_SRC_CHANNEL_ATTRIBUTE_KEY.as_bytes());
+        let mut record = PacketRecord::default();
+        assert!(matches!(
+            incomplete.extract_into(&mut record),
+            Err(ChannelError::MissingAttributeKey { attribute_key })
+                if attribute_key == PKT_SRC_CHANNEL_ATTRIBUTE_KEY
+        ));
+        assert_eq!(record.sequence, 7);
+
+        let mut repeated = send_packet_event();
+        repeated
+            .attributes
+            .push((PKT_SEQ_ATTRIBUTE_KEY, "8").into());
+        assert!(matches!(
+            repeated.extract_into(&mut PacketRecord::default()),
+            Err(ChannelError::DuplicateAttributeKey { attribute_key })
+                if attribute_key == PKT_SEQ_ATTRIBUTE_KEY
+        ));
+
+        let mut other_kind = send_packet_event();
+        other_kind.kind = "recv_packet".to_string();
+        assert!(other_kind
+            .extract_into(&mut PacketRecord::default())
+            .is_err());
+    }
+}
@@ -0,0 +1,37 @@
+//! The keys of the ABCI attributes of channel events, e.g. for referencing
+//! them in relayer filter configurations.
+
+/// Channel handshake event attribute keys
+pub const CONNECTION_ID: &str = "connection_id";
+pub const CHANNEL_ID: &str = "channel_id";
+pub const PORT_ID: &str = "port_id";
+pub const COUNTERPARTY_CHANNEL_ID: &str = "counterparty_channel_id";
+pub const COUNTERPARTY_PORT_ID: &str = "counterparty_port_id";
+pub const VERSION: &str = "version";
+
+/// The attribute key of the module emitting an event, as in ibc-go's
+/// `sdk.AttributeKeyModule`
+pub const MODULE: &str = "module";
+
+/// Packet event attribute keys
+pub const PKT_SEQ: &str = "packet_sequence";
+pub const PKT_DATA: &str = "packet_data";
+pub const PKT_DATA_HEX: &str = "packet_data_hex";
+pub const PKT_DATA_LEN: &str = "packet_data_len";
+pub const PKT_DATA_SHA256: &str = "packet_data_sha256";
+pub const PKT_SRC_PORT: &str = "packet_src_port";
+pub const PKT_SRC_CHANNEL: &str = "packet_src_channel";
+pub const PKT_DST_PORT: &str = "packet_dst_port";
+pub const PKT_DST_CHANNEL: &str = "packet_dst_channel";
+pub const PKT_CHANNEL_ORDERING: &str = "packet_channel_ordering";
+pub const PKT_TIMEOUT_HEIGHT: &str = "packet_timeout_height";
+pub const PKT_TIMEOUT_TIMESTAMP: &str = "packet_timeout_timestamp";
+pub const PKT_ACK: &str = "packet_ack";
+pub const PKT_ACK_HEX: &str = "packet_ack_hex";
+pub const PKT_CONNECTION_ID: &str = "packet_connection";
+/// The non-standard base64-encoded packet data attribute of some bridges.
+#[cfg(feature = "base64-attributes")]
+pub const PKT_DATA_B64: &str = "packet_data_b64";
+/// The combined timeout attribute of very old chains.
+#[cfg(feature = "legacy-attributes")]
+pub const PKT_LEGACY_TIMEOUT: &str = "packet_timeout";
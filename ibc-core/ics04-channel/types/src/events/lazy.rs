@@ -0,0 +1,81 @@
+//! Packet events whose payload is only decoded when accessed, for pipelines
+//! filtering most events on their metadata alone.
+use core::cell::OnceCell;
+
+use ibc_primitives::prelude::*;
+use tendermint::abci;
+
+use super::missing_attribute_key;
+use super::packet_attributes::{
+    PacketDataAttribute, PKT_DATA_ATTRIBUTE_KEY, PKT_DATA_HEX_ATTRIBUTE_KEY,
+};
+use crate::error::ChannelError;
+
+/// A packet event kept in its ABCI form, whose `packet_data_hex` attribute is
+/// only decoded on the first call to [`LazyPacketEvent::packet_data`].
+///
+/// The decoded payload is cached, so that subsequent accesses are free.
+#[derive(Clone, Debug)]
+pub struct LazyPacketEvent {
+    event: abci::Event,
+    packet_data: OnceCell<Vec<u8>>,
+}
+
+impl LazyPacketEvent {
+    pub fn new(event: abci::Event) -> Self {
+        Self {
+            event,
+            packet_data: OnceCell::new(),
+        }
+    }
+
+    pub fn kind(&self) -> &str {
+        &self.event.kind
+    }
+
+    /// Returns the raw value of the first attribute with the given key,
+    /// without decoding it.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.event
+            .attributes
+            .iter()
+            .find(|attr| attr.key_str().is_ok_and(|k| k == key))
+            .and_then(|attr| attr.value_str().ok())
+    }
+
+    /// Decodes the packet data on first access, preferring the hex-encoded
+    /// `packet_data_hex` attribute over the deprecated plaintext one.
+    ///
+    /// A failed decoding is not cached, and is retried on the next access.
+    pub fn packet_data(&self) -> Result<&[u8], ChannelError> {
+        if let Some(packet_data) = self.packet_data.get() {
+            return Ok(packet_data);
+        }
+
+        let attr = self
+            .find_attribute(PKT_DATA_HEX_ATTRIBUTE_KEY)
+            .or_else(|| self.find_attribute(PKT_DATA_ATTRIBUTE_KEY))
+            .ok_or_else(|| missing_attribute_key(PKT_DATA_HEX_ATTRIBUTE_KEY))?;
+        let packet_data =
+            PacketDataAttribute::try_from((attr.key_bytes(), attr.value_bytes()))?.packet_data;
+
+        Ok(self.packet_data.get_or_init(|| packet_data))
+    }
+
+    pub fn into_event(self) -> abci::Event {
+        self.event
+    }
+
+    fn find_attribute(&self, key: &str) -> Option<&abci::EventAttribute> {
+        self.event
+            .attributes
+            .iter()
+            .find(|attr| attr.key_bytes() == key.as_bytes())
+    }
+}
+
+impl From<abci::Event> for LazyPacketEvent {
+    fn from(event: abci::Event) -> Self {
+        Self::new(event)
+    }
+}
@@ -0,0 +1,287 @@
+//! Aggregation of the events of a packet, from its sending to its
+//! acknowledgement or timeout, e.g. for an explorer displaying its lifecycle.
+use ibc_core_host_types::identifiers::{ChannelId, PortId, Sequence};
+use ibc_primitives::prelude::*;
+use tendermint::abci;
+
+use super::{
+    canonical_event_kind, AcknowledgePacket, ReceivePacket, SendPacket, TimeoutPacket,
+    WriteAcknowledgement, ACK_PACKET_EVENT, RECV_PACKET_EVENT, SEND_PACKET_EVENT, TIMEOUT_EVENT,
+    WRITE_ACK_EVENT,
+};
+use crate::error::ChannelError;
+use crate::packet::PacketId;
+
+/// The events observed so far for a single packet. Each event is attached as
+/// it arrives, whatever the order in which the events were observed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PacketLifecycle {
+    pub send: Option<SendPacket>,
+    pub recv: Option<ReceivePacket>,
+    pub write_ack: Option<WriteAcknowledgement>,
+    pub ack: Option<AcknowledgePacket>,
+    pub timeout: Option<TimeoutPacket>,
+}
+
+impl PacketLifecycle {
+    /// Tells whether both ends of the lifecycle were observed, i.e. the
+    /// sending of the packet and either its acknowledgement or its timeout.
+    pub fn is_complete(&self) -> bool {
+        self.send.is_some() && (self.ack.is_some() || self.timeout.is_some())
+    }
+}
+
+/// Folds a stream of packet events into the lifecycles of their packets,
+/// keyed by the [`PacketId`] on the source chain along with the destination
+/// channel, so that the packets of both chains of a channel can be folded
+/// together even when their channels share an identifier.
+///
+/// Events of other kinds are ignored. When an event of a given kind is
+/// observed twice for the same packet, e.g. from two RPC endpoints, the last
+/// one wins.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PacketLifecycles {
+    lifecycles: BTreeMap<(PacketId, ChannelId), PacketLifecycle>,
+}
+
+impl PacketLifecycles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Folds the given events, skipping malformed packet events.
+    pub fn fold<'a>(events: impl IntoIterator<Item = &'a abci::Event>) -> Self {
+        let mut lifecycles = Self::new();
+        for event in events {
+            // Malformed events are skipped, as done by `reconcile`.
+            let _ = lifecycles.observe(event);
+        }
+        lifecycles
+    }
+
+    /// Attaches the event to the lifecycle of its packet, failing if it is a
+    /// malformed packet event.
+    pub fn observe(&mut self, event: &abci::Event) -> Result<(), ChannelError> {
+        match canonical_event_kind(&event.kind) {
+            SEND_PACKET_EVENT => {
+                let send = SendPacket::try_from(event.clone())?;
+                let key = lifecycle_key(
+                    send.port_id_on_a(),
+                    send.chan_id_on_a(),
+                    *send.seq_on_a(),
+                    send.chan_id_on_b(),
+                );
+                self.entry(key).send = Some(send);
+            }
+            RECV_PACKET_EVENT => {
+                let recv = ReceivePacket::try_from(event.clone())?;
+                // The accessors of `ReceivePacket` are named from the point
+                // of view of the receiving chain, so `b` is the source here.
+                let key = lifecycle_key(
+                    recv.port_id_on_b(),
+                    recv.chan_id_on_b(),
+                    *recv.seq_on_b(),
+                    recv.chan_id_on_a(),
+                );
+                self.entry(key).recv = Some(recv);
+            }
+            WRITE_ACK_EVENT => {
+                let write_ack = WriteAcknowledgement::try_from(event.clone())?;
+                let key = lifecycle_key(
+                    write_ack.port_id_on_a(),
+                    write_ack.chan_id_on_a(),
+                    *write_ack.seq_on_a(),
+                    write_ack.chan_id_on_b(),
+                );
+                self.entry(key).write_ack = Some(write_ack);
+            }
+            ACK_PACKET_EVENT => {
+                let ack = AcknowledgePacket::try_from(event.clone())?;
+                let key = lifecycle_key(
+                    ack.port_id_on_a(),
+                    ack.chan_id_on_a(),
+                    *ack.seq_on_a(),
+                    ack.chan_id_on_b(),
+                );
+                self.entry(key).ack = Some(ack);
+            }
+            TIMEOUT_EVENT => {
+                let timeout = TimeoutPacket::try_from(event.clone())?;
+                let key = lifecycle_key(
+                    timeout.port_id_on_a(),
+                    timeout.chan_id_on_a(),
+                    *timeout.seq_on_a(),
+                    timeout.chan_id_on_b(),
+                );
+                self.entry(key).timeout = Some(timeout);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns the lifecycle of the packet with the given id, sent to the
+    /// given destination channel, if any of its events was observed.
+    pub fn get(&self, packet_id: &PacketId, chan_id_on_b: &ChannelId) -> Option<&PacketLifecycle> {
+        self.lifecycles
+            .get(&(packet_id.clone(), chan_id_on_b.clone()))
+    }
+
+    /// Iterates over the lifecycles, ordered by packet id and destination
+    /// channel.
+    pub fn iter(&self) -> impl Iterator<Item = (&(PacketId, ChannelId), &PacketLifecycle)> {
+        self.lifecycles.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lifecycles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lifecycles.is_empty()
+    }
+
+    fn entry(&mut self, key: (PacketId, ChannelId)) -> &mut PacketLifecycle {
+        self.lifecycles.entry(key).or_default()
+    }
+}
+
+fn lifecycle_key(
+    port_id_on_a: &PortId,
+    chan_id_on_a: &ChannelId,
+    seq_on_a: Sequence,
+    chan_id_on_b: &ChannelId,
+) -> (PacketId, ChannelId) {
+    let packet_id = PacketId {
+        port_id: port_id_on_a.clone(),
+        channel_id: chan_id_on_a.clone(),
+        sequence: seq_on_a,
+    };
+
+    (packet_id, chan_id_on_b.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_core_host_types::identifiers::ConnectionId;
+
+    use super::*;
+    use crate::acknowledgement::Acknowledgement;
+    use crate::channel::Order;
+    use crate::events::mock_packet;
+
+    fn packet_id(seq: u64) -> PacketId {
+        PacketId {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::zero(),
+            sequence: seq.into(),
+        }
+    }
+
+    fn lifecycle_events() -> Vec<abci::Event> {
+        let ack = Acknowledgement::new(b"{\"result\":\"AQ==\"}".to_vec()).unwrap();
+        let connection_id = ConnectionId::zero();
+
+        vec![
+            // Packet 1 is received and acknowledged.
+            SendPacket::new(mock_packet(1), Order::Unordered, connection_id.clone())
+                .try_into()
+                .unwrap(),
+            ReceivePacket::new(mock_packet(1), Order::Unordered, connection_id.clone())
+                .try_into()
+                .unwrap(),
+            WriteAcknowledgement::new(mock_packet(1), ack, connection_id.clone())
+                .try_into()
+                .unwrap(),
+            AcknowledgePacket::new(mock_packet(1), Order::Unordered, connection_id.clone())
+                .try_into()
+                .unwrap(),
+            // Packet 2 times out.
+            SendPacket::new(mock_packet(2), Order::Unordered, connection_id.clone())
+                .try_into()
+                .unwrap(),
+            TimeoutPacket::new(mock_packet(2), Order::Unordered)
+                .try_into()
+                .unwrap(),
+            // The sending of packet 3 was not observed.
+            ReceivePacket::new(mock_packet(3), Order::Unordered, connection_id)
+                .try_into()
+                .unwrap(),
+            abci::Event {
+                kind: "message".to_string(),
+                attributes: vec![("module", "ibc_channel").into()],
+            },
+        ]
+    }
+
+    #[test]
+    fn fold_shuffled_lifecycle_events() {
+        let events = lifecycle_events();
+        let shuffled: Vec<abci::Event> = [5, 3, 7, 0, 6, 2, 4, 1]
+            .into_iter()
+            .map(|index| events[index].clone())
+            .collect();
+
+        let lifecycles = PacketLifecycles::fold(&shuffled);
+        assert_eq!(lifecycles, PacketLifecycles::fold(&events));
+        assert_eq!(lifecycles.len(), 3);
+
+        let first = lifecycles.get(&packet_id(1), &ChannelId::new(1)).unwrap();
+        assert!(first.is_complete());
+        assert!(first.recv.is_some() && first.write_ack.is_some());
+        assert!(first.timeout.is_none());
+
+        let second = lifecycles.get(&packet_id(2), &ChannelId::new(1)).unwrap();
+        assert!(second.is_complete());
+        assert!(second.recv.is_none() && second.ack.is_none());
+        assert!(second.timeout.is_some());
+
+        let third = lifecycles.get(&packet_id(3), &ChannelId::new(1)).unwrap();
+        assert!(!third.is_complete());
+        assert!(third.send.is_none() && third.recv.is_some());
+
+        assert!(lifecycles.get(&packet_id(4), &ChannelId::new(1)).is_none());
+    }
+
+    #[test]
+    fn fold_packets_of_both_chains() {
+        // Packets with the same id, sent to different channels, e.g. by both
+        // chains of a channel whose ends are both `channel-0`.
+        let chan_ids_on_b = [ChannelId::zero(), ChannelId::new(1), ChannelId::new(2)];
+        let events: Vec<abci::Event> = chan_ids_on_b
+            .iter()
+            .map(|chan_id_on_b| {
+                let mut packet = mock_packet(1);
+                packet.chan_id_on_b = chan_id_on_b.clone();
+                SendPacket::new(packet, Order::Unordered, ConnectionId::zero())
+                    .try_into()
+                    .unwrap()
+            })
+            .collect();
+
+        let lifecycles = PacketLifecycles::fold(&events);
+        assert_eq!(lifecycles.len(), 3);
+        for chan_id_on_b in &chan_ids_on_b {
+            let send = lifecycles
+                .get(&packet_id(1), chan_id_on_b)
+                .unwrap()
+                .send
+                .as_ref();
+            assert_eq!(send.unwrap().chan_id_on_b(), chan_id_on_b);
+        }
+    }
+
+    #[test]
+    fn observe_malformed_packet_event() {
+        let mut event = lifecycle_events().swap_remove(0);
+        event
+            .attributes
+            .retain(|attr| attr.key_str().unwrap() != "packet_sequence");
+
+        let mut lifecycles = PacketLifecycles::new();
+        assert!(lifecycles.observe(&event).is_err());
+        assert!(lifecycles.is_empty());
+        assert!(PacketLifecycles::fold(&[event]).is_empty());
+    }
+}
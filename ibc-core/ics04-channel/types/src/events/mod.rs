@@ -1,46 +1,382 @@
 //! Types for the IBC events emitted from Tendermint Websocket by the channels module.
 
+/// Implements `validate` for attributes emitted as a single ABCI attribute, by
+/// parsing back their emitted form through the regular `TryFrom` path.
+macro_rules! impl_validate_by_reparsing {
+    ($($attribute:ty),+ $(,)?) => {
+        $(
+            impl $attribute {
+                /// Re-checks the invariants enforced when parsing the attribute,
+                /// e.g. after decoding it with a non-validating codec.
+                pub fn validate(&self) -> Result<(), ChannelError> {
+                    Self::try_from(abci::EventAttribute::from(self.clone())).map(|_| ())
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `AttributeKey` for attributes emitted as a single ABCI attribute
+/// with the given key.
+macro_rules! impl_attribute_key {
+    ($($attribute:ty => $key:expr),+ $(,)?) => {
+        $(
+            impl super::attribute_map::AttributeKey for $attribute {
+                const KEY: &'static str = $key;
+            }
+        )+
+    };
+}
+
+/// Implements emission by value, and through `to_attribute`, for attributes
+/// whose emission is implemented from a borrow, i.e. which format their value
+/// without cloning it.
+macro_rules! impl_borrowed_emission {
+    ($($attribute:ty),+ $(,)?) => {
+        $(
+            impl From<$attribute> for abci::EventAttribute {
+                fn from(attr: $attribute) -> Self {
+                    Self::from(&attr)
+                }
+            }
+
+            impl $attribute {
+                /// Emits the attribute without consuming it.
+                pub fn to_attribute(&self) -> abci::EventAttribute {
+                    self.into()
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "abci-proto")]
+pub mod abci_proto;
+#[cfg(any(test, feature = "testkit"))]
+mod arbitrary;
+mod attribute_map;
 mod channel_attributes;
-mod packet_attributes;
+mod emission_profile;
+mod extract;
+pub mod keys;
+mod lazy;
+mod lifecycle;
+pub mod packet_attributes;
+mod schema_drift;
+#[cfg(feature = "serde")]
+mod versioned;
+
+use alloc::collections::BTreeSet;
+use core::str::FromStr;
 
+use ibc_core_client_types::Height;
 use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId, Sequence};
 use ibc_primitives::prelude::*;
 use ibc_primitives::Timestamp;
 use tendermint::abci;
 
+use self::attribute_map::{AttributeKey, AttributeMap};
 use self::channel_attributes::{
     ChannelIdAttribute, ConnectionIdAttribute, CounterpartyChannelIdAttribute,
     CounterpartyPortIdAttribute, PortIdAttribute, VersionAttribute,
     COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY,
 };
 use self::packet_attributes::{
-    AcknowledgementAttribute, ChannelOrderingAttribute, DstChannelIdAttribute, DstPortIdAttribute,
-    PacketConnectionIdAttribute, PacketDataAttribute, SequenceAttribute, SrcChannelIdAttribute,
+    required_attribute, AcknowledgementAttribute, ChannelOrderingAttribute, DstChannelIdAttribute,
+    DstPortIdAttribute, ExtraAttributes, PacketConnectionIdAttribute, PacketDataAttribute,
+    PacketEventAttributes, PacketTimeout, SequenceAttribute, SrcChannelIdAttribute,
     SrcPortIdAttribute, TimeoutHeightAttribute, TimeoutTimestampAttribute,
+    PKT_ACK_HEX_ATTRIBUTE_KEY, PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY, PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+    PKT_DATA_HEX_ATTRIBUTE_KEY, PKT_DST_CHANNEL_ATTRIBUTE_KEY, PKT_DST_PORT_ATTRIBUTE_KEY,
+    PKT_SEQ_ATTRIBUTE_KEY, PKT_SRC_CHANNEL_ATTRIBUTE_KEY, PKT_SRC_PORT_ATTRIBUTE_KEY,
+    PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY, PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
 };
 use super::acknowledgement::Acknowledgement;
 use super::channel::Order;
 use super::timeout::TimeoutHeight;
 use super::Version;
-use crate::error::ChannelError;
+use crate::commitment::{compute_packet_commitment, PacketCommitment};
+use crate::error::{AttributeValueError, ChannelError, PacketError};
 use crate::packet::Packet;
 
+pub use self::channel_attributes::{ChannelEndpoint, ModuleAttribute};
+pub use self::emission_profile::EmissionProfile;
+pub use self::extract::{ExtractInto, SendPacketFields};
+pub use self::lazy::LazyPacketEvent;
+pub use self::lifecycle::{PacketLifecycle, PacketLifecycles};
+pub use self::schema_drift::{SchemaDrift, SchemaDriftDetector};
+#[cfg(feature = "serde")]
+pub use self::versioned::{SendPacketV1, VersionedSendPacket};
+
 /// Channel event types corresponding to ibc-go's channel events:
 /// https://github.com/cosmos/ibc-go/blob/c4413c5877f9ef883494da1721cb18caaba7f7f5/modules/core/04-channel/types/events.go#L52-L72
-const CHANNEL_OPEN_INIT_EVENT: &str = "channel_open_init";
-const CHANNEL_OPEN_TRY_EVENT: &str = "channel_open_try";
-const CHANNEL_OPEN_ACK_EVENT: &str = "channel_open_ack";
-const CHANNEL_OPEN_CONFIRM_EVENT: &str = "channel_open_confirm";
-const CHANNEL_CLOSE_INIT_EVENT: &str = "channel_close_init";
-const CHANNEL_CLOSE_CONFIRM_EVENT: &str = "channel_close_confirm";
-const CHANNEL_CLOSED_EVENT: &str = "channel_close";
+pub const CHANNEL_OPEN_INIT_EVENT: &str = "channel_open_init";
+pub const CHANNEL_OPEN_TRY_EVENT: &str = "channel_open_try";
+pub const CHANNEL_OPEN_ACK_EVENT: &str = "channel_open_ack";
+pub const CHANNEL_OPEN_CONFIRM_EVENT: &str = "channel_open_confirm";
+pub const CHANNEL_CLOSE_INIT_EVENT: &str = "channel_close_init";
+pub const CHANNEL_CLOSE_CONFIRM_EVENT: &str = "channel_close_confirm";
+pub const CHANNEL_CLOSED_EVENT: &str = "channel_close";
 
 /// Packet event types
-const SEND_PACKET_EVENT: &str = "send_packet";
-const RECEIVE_PACKET_EVENT: &str = "recv_packet";
-const WRITE_ACK_EVENT: &str = "write_acknowledgement";
-const ACK_PACKET_EVENT: &str = "acknowledge_packet";
-const TIMEOUT_EVENT: &str = "timeout_packet";
+pub const SEND_PACKET_EVENT: &str = "send_packet";
+pub const RECV_PACKET_EVENT: &str = "recv_packet";
+pub const WRITE_ACK_EVENT: &str = "write_acknowledgement";
+pub const ACK_PACKET_EVENT: &str = "acknowledge_packet";
+pub const TIMEOUT_EVENT: &str = "timeout_packet";
+
+/// Historical event kinds emitted by older chains, as `(canonical, alias)`
+/// pairs. Packet events are parsed under either kind.
+pub const EVENT_KIND_ALIASES: &[(&str, &str)] =
+    &[(WRITE_ACK_EVENT, "write_acknowledgement_packet")];
+
+/// Resolves a historical event kind to its canonical form. Other kinds are
+/// returned unchanged.
+pub fn canonical_event_kind(kind: &str) -> &str {
+    EVENT_KIND_ALIASES
+        .iter()
+        .find(|(_, alias)| *alias == kind)
+        .map_or(kind, |(canonical, _)| canonical)
+}
+
+/// The `(key, value)` predicates selecting the events of kind `event_kind`
+/// emitted on the given channel end, for building e.g. a `tendermint-rpc`
+/// subscription query.
+///
+/// Keys are qualified with the event kind, as in `send_packet.packet_src_port`.
+/// Packet events are matched on the packet end living on the emitting chain,
+/// i.e. the source end for `send_packet`, `acknowledge_packet` and
+/// `timeout_packet`, and the destination end for `recv_packet` and
+/// `write_acknowledgement`. Returns `None` for kinds other than the channel
+/// ones.
+pub fn subscription_predicates(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    event_kind: &str,
+) -> Option<Vec<(String, String)>> {
+    let (port_key, channel_key) = match canonical_event_kind(event_kind) {
+        SEND_PACKET_EVENT | ACK_PACKET_EVENT | TIMEOUT_EVENT => {
+            (keys::PKT_SRC_PORT, keys::PKT_SRC_CHANNEL)
+        }
+        RECV_PACKET_EVENT | WRITE_ACK_EVENT => (keys::PKT_DST_PORT, keys::PKT_DST_CHANNEL),
+        CHANNEL_OPEN_INIT_EVENT
+        | CHANNEL_OPEN_TRY_EVENT
+        | CHANNEL_OPEN_ACK_EVENT
+        | CHANNEL_OPEN_CONFIRM_EVENT
+        | CHANNEL_CLOSE_INIT_EVENT
+        | CHANNEL_CLOSE_CONFIRM_EVENT
+        | CHANNEL_CLOSED_EVENT => (keys::PORT_ID, keys::CHANNEL_ID),
+        _ => return None,
+    };
+
+    Some(vec![
+        (format!("{event_kind}.{port_key}"), port_id.to_string()),
+        (
+            format!("{event_kind}.{channel_key}"),
+            channel_id.to_string(),
+        ),
+    ])
+}
+
+/// Extracts the connection identifier of a packet event (`packet_connection`)
+/// or of a channel handshake event (`connection_id`), whatever its kind.
+///
+/// Returns `None` if the event has no such attribute, or if it is malformed.
+pub fn connection_id_of(event: &abci::Event) -> Option<ConnectionId> {
+    let attrs = AttributeMap::from(event.attributes.as_slice());
+
+    match attrs.get_optional::<PacketConnectionIdAttribute>() {
+        Ok(Some(attr)) => Some(attr.connection_id),
+        Ok(None) => attrs
+            .get_optional::<ConnectionIdAttribute>()
+            .ok()
+            .flatten()
+            .map(|attr| attr.connection_id),
+        Err(_) => None,
+    }
+}
+
+/// Renders a compact one-line summary of an event for debugging, e.g.
+/// `SendPacket seq=5 channel-0->channel-1 timeout=3-1000`.
+///
+/// Channel events are summarized by the attributes identifying them, any of
+/// which renders as `?` if missing or malformed. Other events are dumped as
+/// their kind followed by their `key=value` attributes.
+pub fn describe(event: &abci::Event) -> String {
+    fn show<T: AttributeKey>(attrs: &AttributeMap<'_>, render: impl FnOnce(T) -> String) -> String {
+        attrs.get::<T>().map_or_else(|_| "?".to_string(), render)
+    }
+
+    let attrs = AttributeMap::from(event.attributes.as_slice());
+    let kind = canonical_event_kind(&event.kind);
+
+    let packet_event = match kind {
+        SEND_PACKET_EVENT => Some("SendPacket"),
+        RECV_PACKET_EVENT => Some("ReceivePacket"),
+        WRITE_ACK_EVENT => Some("WriteAcknowledgement"),
+        ACK_PACKET_EVENT => Some("AcknowledgePacket"),
+        TIMEOUT_EVENT => Some("TimeoutPacket"),
+        _ => None,
+    };
+    if let Some(name) = packet_event {
+        return format!(
+            "{name} seq={} {}->{} timeout={}",
+            show(&attrs, |attr: SequenceAttribute| attr.sequence.to_string()),
+            show(&attrs, |attr: SrcChannelIdAttribute| attr
+                .src_channel_id
+                .to_string()),
+            show(&attrs, |attr: DstChannelIdAttribute| attr
+                .dst_channel_id
+                .to_string()),
+            show(&attrs, |attr: TimeoutHeightAttribute| {
+                match attr.timeout_height {
+                    TimeoutHeight::At(height) => height.to_string(),
+                    TimeoutHeight::Never => "never".to_string(),
+                }
+            }),
+        );
+    }
+
+    let handshake_event = match kind {
+        CHANNEL_OPEN_INIT_EVENT => Some("ChannelOpenInit"),
+        CHANNEL_OPEN_TRY_EVENT => Some("ChannelOpenTry"),
+        CHANNEL_OPEN_ACK_EVENT => Some("ChannelOpenAck"),
+        CHANNEL_OPEN_CONFIRM_EVENT => Some("ChannelOpenConfirm"),
+        CHANNEL_CLOSE_INIT_EVENT => Some("ChannelCloseInit"),
+        CHANNEL_CLOSE_CONFIRM_EVENT => Some("ChannelCloseConfirm"),
+        CHANNEL_CLOSED_EVENT => Some("ChannelClosed"),
+        _ => None,
+    };
+    if let Some(name) = handshake_event {
+        return format!(
+            "{name} {}/{}->{}/{} connection={}",
+            show(&attrs, |attr: PortIdAttribute| attr.port_id.to_string()),
+            show(&attrs, |attr: ChannelIdAttribute| attr
+                .channel_id
+                .to_string()),
+            show(&attrs, |attr: CounterpartyPortIdAttribute| {
+                attr.counterparty_port_id.to_string()
+            }),
+            show(&attrs, |attr: CounterpartyChannelIdAttribute| {
+                attr.counterparty_channel_id.to_string()
+            }),
+            show(&attrs, |attr: ConnectionIdAttribute| attr
+                .connection_id
+                .to_string()),
+        );
+    }
+
+    let mut summary = event.kind.clone();
+    for attr in &event.attributes {
+        let key = attr.key_str().unwrap_or("?");
+        let value = attr.value_str().unwrap_or("?");
+        summary.push_str(&format!(" {key}={value}"));
+    }
+    summary
+}
+
+/// Compares an attribute key against the expected one.
+///
+/// With the `lenient-attributes` feature enabled the comparison ignores ASCII
+/// case, to accommodate chains emitting keys such as `Packet_Sequence`.
+fn attribute_key_matches(key: &str, expected: &str) -> bool {
+    #[cfg(feature = "lenient-attributes")]
+    {
+        key.eq_ignore_ascii_case(expected)
+    }
+    #[cfg(not(feature = "lenient-attributes"))]
+    {
+        key == expected
+    }
+}
+
+/// Strips a single trailing null byte from an identifier or numeric attribute
+/// value when the `null-terminated-values` feature is enabled, to accommodate
+/// chains that null-terminate them. Otherwise the value is returned unchanged.
+fn strip_trailing_null(value: &[u8]) -> &[u8] {
+    #[cfg(feature = "null-terminated-values")]
+    {
+        value.strip_suffix(b"\0").unwrap_or(value)
+    }
+    #[cfg(not(feature = "null-terminated-values"))]
+    {
+        value
+    }
+}
+
+/// Checks that the raw key/value attribute pair carries the `expected_key` and
+/// returns its value.
+fn attribute_value<'a>(
+    (key, value): (&[u8], &'a [u8]),
+    expected_key: &str,
+) -> Result<&'a str, ChannelError> {
+    let key_str = str::from_utf8(key).map_err(|_| {
+        record_parse_error(ChannelError::InvalidAttributeKey {
+            attribute_key: String::new(),
+        })
+    })?;
+
+    if !attribute_key_matches(key_str, expected_key) {
+        return Err(record_parse_error(ChannelError::InvalidAttributeKey {
+            attribute_key: key_str.to_string(),
+        }));
+    }
+
+    str::from_utf8(value).map_err(|e| invalid_attribute_value("", e))
+}
+
+/// Like [`attribute_value`], but trims leading and trailing ASCII whitespace
+/// from the value, as some chains pad their values, e.g. with a trailing
+/// newline, and strips a trailing null byte (see [`strip_trailing_null`]).
+///
+/// Only meant for numeric and identifier values, which never legitimately
+/// contain whitespace or null bytes. Packet data and acknowledgements must be
+/// byte-exact.
+fn trimmed_attribute_value<'a>(
+    (key, value): (&[u8], &'a [u8]),
+    expected_key: &str,
+) -> Result<&'a str, ChannelError> {
+    attribute_value((key, strip_trailing_null(value)), expected_key)
+        .map(|value| value.trim_matches(|c: char| c.is_ascii_whitespace()))
+}
+
+/// Parses the numeric or identifier attribute value with `FromStr`, after
+/// trimming surrounding whitespace, reporting the raw value and the
+/// underlying parse error on failure.
+fn parse_attribute_value<T>(attr: (&[u8], &[u8]), expected_key: &str) -> Result<T, ChannelError>
+where
+    T: FromStr,
+    T::Err: Into<AttributeValueError>,
+{
+    let value = trimmed_attribute_value(attr, expected_key)?;
+
+    value.parse().map_err(|e| invalid_attribute_value(value, e))
+}
+
+/// Builds (and records) the error of an attribute value that failed to parse.
+fn invalid_attribute_value(value: &str, error: impl Into<AttributeValueError>) -> ChannelError {
+    record_parse_error(ChannelError::InvalidAttributeValue {
+        attribute_value: value.to_string(),
+        error: Some(error.into()),
+    })
+}
+
+/// Builds (and records) the error of a required attribute missing from an
+/// event.
+fn missing_attribute_key(key: &str) -> ChannelError {
+    record_parse_error(ChannelError::MissingAttributeKey {
+        attribute_key: key.to_string(),
+    })
+}
+
+/// Reports a failed attribute conversion to the installed metrics recorder,
+/// if any, and hands the error back.
+fn record_parse_error(err: ChannelError) -> ChannelError {
+    #[cfg(feature = "metrics")]
+    crate::metrics::record_attribute_parse_error(&err);
+
+    err
+}
 
 #[cfg_attr(
     feature = "parity-scale-codec",
@@ -62,6 +398,12 @@ pub struct OpenInit {
     port_id_attr_on_b: CounterpartyPortIdAttribute,
     conn_id_attr_on_a: ConnectionIdAttribute,
     version_attr_on_a: VersionAttribute,
+    /// The `module` attribute attached by the emitting chain, if any.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    module_attr: Option<ModuleAttribute>,
 }
 
 impl OpenInit {
@@ -78,6 +420,7 @@ impl OpenInit {
             port_id_attr_on_b: port_id_on_b.into(),
             conn_id_attr_on_a: conn_id_on_a.into(),
             version_attr_on_a: version_on_a.into(),
+            module_attr: None,
         }
     }
     pub fn port_id_on_a(&self) -> &PortId {
@@ -96,23 +439,72 @@ impl OpenInit {
         &self.version_attr_on_a.version
     }
 
-    pub fn event_type(&self) -> &str {
+    /// Attaches the name of the module emitting the event, emitted as the
+    /// `module` attribute.
+    pub fn with_module(mut self, module: String) -> Self {
+        self.module_attr = Some(module.into());
+        self
+    }
+
+    pub fn module(&self) -> Option<&str> {
+        self.module_attr.as_ref().map(|attr| attr.module.as_str())
+    }
+
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
         CHANNEL_OPEN_INIT_EVENT
     }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
+}
+
+/// A valid `OpenInit` event on the transfer port of `connection-0`, with the
+/// `ics20-1` version and no counterparty channel yet.
+#[cfg(any(test, feature = "testkit"))]
+pub fn mock_open_init() -> OpenInit {
+    OpenInit::new(
+        PortId::transfer(),
+        ChannelId::zero(),
+        PortId::transfer(),
+        ConnectionId::zero(),
+        Version::new("ics20-1".to_string()),
+    )
+}
+
+/// A packet with the given sequence from `transfer/channel-0` to
+/// `transfer/channel-1`, carrying an ICS-20 like `{"amount":"100"}` payload,
+/// with no timeout height and a timeout timestamp of 1000 nanoseconds.
+#[cfg(any(test, feature = "testkit"))]
+pub fn mock_packet(seq: u64) -> Packet {
+    Packet {
+        seq_on_a: seq.into(),
+        port_id_on_a: PortId::transfer(),
+        chan_id_on_a: ChannelId::zero(),
+        port_id_on_b: PortId::transfer(),
+        chan_id_on_b: ChannelId::new(1),
+        data: b"{\"amount\":\"100\"}".to_vec(),
+        timeout_height_on_b: TimeoutHeight::Never,
+        timeout_timestamp_on_b: Timestamp::from_nanoseconds(1_000).expect("valid timestamp"),
+    }
 }
 
 impl From<OpenInit> for abci::Event {
     fn from(o: OpenInit) -> Self {
+        let mut attributes = vec![
+            o.port_id_attr_on_a.into(),
+            o.chan_id_attr_on_a.into(),
+            o.port_id_attr_on_b.into(),
+            (COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY, "").into(),
+            o.conn_id_attr_on_a.into(),
+            o.version_attr_on_a.into(),
+        ];
+        attributes.extend(o.module_attr.map(abci::EventAttribute::from));
+
         abci::Event {
             kind: CHANNEL_OPEN_INIT_EVENT.to_string(),
-            attributes: vec![
-                o.port_id_attr_on_a.into(),
-                o.chan_id_attr_on_a.into(),
-                o.port_id_attr_on_b.into(),
-                (COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY, "").into(),
-                o.conn_id_attr_on_a.into(),
-                o.version_attr_on_a.into(),
-            ],
+            attributes,
         }
     }
 }
@@ -138,6 +530,12 @@ pub struct OpenTry {
     chan_id_attr_on_a: CounterpartyChannelIdAttribute,
     conn_id_attr_on_b: ConnectionIdAttribute,
     version_attr_on_b: VersionAttribute,
+    /// The `module` attribute attached by the emitting chain, if any.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    module_attr: Option<ModuleAttribute>,
 }
 
 impl OpenTry {
@@ -156,6 +554,7 @@ impl OpenTry {
             chan_id_attr_on_a: chan_id_on_a.into(),
             conn_id_attr_on_b: conn_id_on_b.into(),
             version_attr_on_b: version_on_b.into(),
+            module_attr: None,
         }
     }
     pub fn port_id_on_b(&self) -> &PortId {
@@ -177,23 +576,42 @@ impl OpenTry {
         &self.version_attr_on_b.version
     }
 
-    pub fn event_type(&self) -> &str {
+    /// Attaches the name of the module emitting the event, emitted as the
+    /// `module` attribute.
+    pub fn with_module(mut self, module: String) -> Self {
+        self.module_attr = Some(module.into());
+        self
+    }
+
+    pub fn module(&self) -> Option<&str> {
+        self.module_attr.as_ref().map(|attr| attr.module.as_str())
+    }
+
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
         CHANNEL_OPEN_TRY_EVENT
     }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
 }
 
 impl From<OpenTry> for abci::Event {
     fn from(o: OpenTry) -> Self {
+        let mut attributes = vec![
+            o.port_id_attr_on_b.into(),
+            o.chan_id_attr_on_b.into(),
+            o.port_id_attr_on_a.into(),
+            o.chan_id_attr_on_a.into(),
+            o.conn_id_attr_on_b.into(),
+            o.version_attr_on_b.into(),
+        ];
+        attributes.extend(o.module_attr.map(abci::EventAttribute::from));
+
         abci::Event {
             kind: CHANNEL_OPEN_TRY_EVENT.to_string(),
-            attributes: vec![
-                o.port_id_attr_on_b.into(),
-                o.chan_id_attr_on_b.into(),
-                o.port_id_attr_on_a.into(),
-                o.chan_id_attr_on_a.into(),
-                o.conn_id_attr_on_b.into(),
-                o.version_attr_on_b.into(),
-            ],
+            attributes,
         }
     }
 }
@@ -218,6 +636,12 @@ pub struct OpenAck {
     port_id_attr_on_b: CounterpartyPortIdAttribute,
     chan_id_attr_on_b: CounterpartyChannelIdAttribute,
     conn_id_attr_on_a: ConnectionIdAttribute,
+    /// The `module` attribute attached by the emitting chain, if any.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    module_attr: Option<ModuleAttribute>,
 }
 
 impl OpenAck {
@@ -234,6 +658,7 @@ impl OpenAck {
             port_id_attr_on_b: port_id_on_b.into(),
             chan_id_attr_on_b: chan_id_on_b.into(),
             conn_id_attr_on_a: conn_id_on_a.into(),
+            module_attr: None,
         }
     }
     pub fn port_id_on_a(&self) -> &PortId {
@@ -252,22 +677,41 @@ impl OpenAck {
         &self.conn_id_attr_on_a.connection_id
     }
 
-    pub fn event_type(&self) -> &str {
+    /// Attaches the name of the module emitting the event, emitted as the
+    /// `module` attribute.
+    pub fn with_module(mut self, module: String) -> Self {
+        self.module_attr = Some(module.into());
+        self
+    }
+
+    pub fn module(&self) -> Option<&str> {
+        self.module_attr.as_ref().map(|attr| attr.module.as_str())
+    }
+
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
         CHANNEL_OPEN_ACK_EVENT
     }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
 }
 
 impl From<OpenAck> for abci::Event {
     fn from(o: OpenAck) -> Self {
+        let mut attributes = vec![
+            o.port_id_attr_on_a.into(),
+            o.chan_id_attr_on_a.into(),
+            o.port_id_attr_on_b.into(),
+            o.chan_id_attr_on_b.into(),
+            o.conn_id_attr_on_a.into(),
+        ];
+        attributes.extend(o.module_attr.map(abci::EventAttribute::from));
+
         abci::Event {
             kind: CHANNEL_OPEN_ACK_EVENT.to_string(),
-            attributes: vec![
-                o.port_id_attr_on_a.into(),
-                o.chan_id_attr_on_a.into(),
-                o.port_id_attr_on_b.into(),
-                o.chan_id_attr_on_b.into(),
-                o.conn_id_attr_on_a.into(),
-            ],
+            attributes,
         }
     }
 }
@@ -292,6 +736,12 @@ pub struct OpenConfirm {
     port_id_attr_on_a: CounterpartyPortIdAttribute,
     chan_id_attr_on_a: CounterpartyChannelIdAttribute,
     conn_id_attr_on_b: ConnectionIdAttribute,
+    /// The `module` attribute attached by the emitting chain, if any.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    module_attr: Option<ModuleAttribute>,
 }
 
 impl OpenConfirm {
@@ -308,6 +758,7 @@ impl OpenConfirm {
             port_id_attr_on_a: port_id_on_a.into(),
             chan_id_attr_on_a: chan_id_on_a.into(),
             conn_id_attr_on_b: conn_id_on_b.into(),
+            module_attr: None,
         }
     }
     pub fn port_id_on_b(&self) -> &PortId {
@@ -326,22 +777,41 @@ impl OpenConfirm {
         &self.conn_id_attr_on_b.connection_id
     }
 
-    pub fn event_type(&self) -> &str {
+    /// Attaches the name of the module emitting the event, emitted as the
+    /// `module` attribute.
+    pub fn with_module(mut self, module: String) -> Self {
+        self.module_attr = Some(module.into());
+        self
+    }
+
+    pub fn module(&self) -> Option<&str> {
+        self.module_attr.as_ref().map(|attr| attr.module.as_str())
+    }
+
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
         CHANNEL_OPEN_CONFIRM_EVENT
     }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
 }
 
 impl From<OpenConfirm> for abci::Event {
     fn from(o: OpenConfirm) -> Self {
+        let mut attributes = vec![
+            o.port_id_attr_on_b.into(),
+            o.chan_id_attr_on_b.into(),
+            o.port_id_attr_on_a.into(),
+            o.chan_id_attr_on_a.into(),
+            o.conn_id_attr_on_b.into(),
+        ];
+        attributes.extend(o.module_attr.map(abci::EventAttribute::from));
+
         abci::Event {
             kind: CHANNEL_OPEN_CONFIRM_EVENT.to_string(),
-            attributes: vec![
-                o.port_id_attr_on_b.into(),
-                o.chan_id_attr_on_b.into(),
-                o.port_id_attr_on_a.into(),
-                o.chan_id_attr_on_a.into(),
-                o.conn_id_attr_on_b.into(),
-            ],
+            attributes,
         }
     }
 }
@@ -400,9 +870,14 @@ impl CloseInit {
         &self.conn_id_attr_on_a.connection_id
     }
 
-    pub fn event_type(&self) -> &str {
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
         CHANNEL_CLOSE_INIT_EVENT
     }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
 }
 
 impl From<CloseInit> for abci::Event {
@@ -474,9 +949,14 @@ impl CloseConfirm {
         &self.conn_id_attr_on_b.connection_id
     }
 
-    pub fn event_type(&self) -> &str {
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
         CHANNEL_CLOSE_CONFIRM_EVENT
     }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
 }
 
 impl From<CloseConfirm> for abci::Event {
@@ -494,6 +974,130 @@ impl From<CloseConfirm> for abci::Event {
     }
 }
 
+/// Any of the events emitted during the channel opening or closing handshake.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, derive_more::From)]
+pub enum ChannelHandshakeEvent {
+    OpenInit(OpenInit),
+    OpenTry(OpenTry),
+    OpenAck(OpenAck),
+    OpenConfirm(OpenConfirm),
+    CloseInit(CloseInit),
+    CloseConfirm(CloseConfirm),
+}
+
+impl ChannelHandshakeEvent {
+    /// Returns the port identifier of the chain that emitted the event.
+    pub fn port_id(&self) -> &PortId {
+        match self {
+            Self::OpenInit(ev) => ev.port_id_on_a(),
+            Self::OpenTry(ev) => ev.port_id_on_b(),
+            Self::OpenAck(ev) => ev.port_id_on_a(),
+            Self::OpenConfirm(ev) => ev.port_id_on_b(),
+            Self::CloseInit(ev) => ev.port_id_on_a(),
+            Self::CloseConfirm(ev) => ev.port_id_on_b(),
+        }
+    }
+
+    /// Returns the channel identifier of the chain that emitted the event.
+    pub fn chan_id(&self) -> &ChannelId {
+        match self {
+            Self::OpenInit(ev) => ev.chan_id_on_a(),
+            Self::OpenTry(ev) => ev.chan_id_on_b(),
+            Self::OpenAck(ev) => ev.chan_id_on_a(),
+            Self::OpenConfirm(ev) => ev.chan_id_on_b(),
+            Self::CloseInit(ev) => ev.chan_id_on_a(),
+            Self::CloseConfirm(ev) => ev.chan_id_on_b(),
+        }
+    }
+
+    /// Returns the connection identifier of the chain that emitted the event.
+    pub fn conn_id(&self) -> &ConnectionId {
+        match self {
+            Self::OpenInit(ev) => ev.conn_id_on_a(),
+            Self::OpenTry(ev) => ev.conn_id_on_b(),
+            Self::OpenAck(ev) => ev.conn_id_on_a(),
+            Self::OpenConfirm(ev) => ev.conn_id_on_b(),
+            Self::CloseInit(ev) => ev.conn_id_on_a(),
+            Self::CloseConfirm(ev) => ev.conn_id_on_b(),
+        }
+    }
+
+    pub fn event_type(&self) -> &str {
+        match self {
+            Self::OpenInit(ev) => ev.event_type(),
+            Self::OpenTry(ev) => ev.event_type(),
+            Self::OpenAck(ev) => ev.event_type(),
+            Self::OpenConfirm(ev) => ev.event_type(),
+            Self::CloseInit(ev) => ev.event_type(),
+            Self::CloseConfirm(ev) => ev.event_type(),
+        }
+    }
+}
+
+impl From<ChannelHandshakeEvent> for abci::Event {
+    fn from(ev: ChannelHandshakeEvent) -> Self {
+        match ev {
+            ChannelHandshakeEvent::OpenInit(ev) => ev.into(),
+            ChannelHandshakeEvent::OpenTry(ev) => ev.into(),
+            ChannelHandshakeEvent::OpenAck(ev) => ev.into(),
+            ChannelHandshakeEvent::OpenConfirm(ev) => ev.into(),
+            ChannelHandshakeEvent::CloseInit(ev) => ev.into(),
+            ChannelHandshakeEvent::CloseConfirm(ev) => ev.into(),
+        }
+    }
+}
+
+/// Checks that the port of the chain emitting a handshake event is bound to
+/// one of the `registered` applications.
+pub fn validate_handshake_port(
+    event: &ChannelHandshakeEvent,
+    registered: &BTreeSet<PortId>,
+) -> Result<(), ChannelError> {
+    let port_id = event.port_id();
+
+    if !registered.contains(port_id) {
+        return Err(ChannelError::UnknownPort {
+            port_id: port_id.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that the `ordering` of the channel `chan_id` is among the `features`
+/// advertised by the underlying connection version, e.g. `ORDER_ORDERED` or
+/// `ORDER_UNORDERED`.
+///
+/// Handshake events do not carry the channel ordering, so it has to be taken
+/// from the handshake message or the stored channel end.
+pub fn validate_ordering_supported(
+    chan_id: &ChannelId,
+    ordering: Order,
+    features: &[String],
+) -> Result<(), ChannelError> {
+    if !features.iter().any(|feature| feature == ordering.as_str()) {
+        return Err(ChannelError::OrderingNotSupported {
+            channel_id: chan_id.clone(),
+            ordering,
+        });
+    }
+
+    Ok(())
+}
+
 /// A `ChannelClosed` event is emitted when a channel is closed as a result of a packet timing out. Note that
 /// since optimistic packet sends (i.e. send a packet before channel handshake is complete) are supported,
 /// we might not have a counterparty channel id value yet. This would happen if a packet is sent right
@@ -558,9 +1162,14 @@ impl ChannelClosed {
         &self.channel_ordering_attr.order
     }
 
-    pub fn event_type(&self) -> &str {
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
         CHANNEL_CLOSED_EVENT
     }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
 }
 
 impl From<ChannelClosed> for abci::Event {
@@ -607,6 +1216,15 @@ pub struct SendPacket {
     chan_id_attr_on_b: DstChannelIdAttribute,
     channel_ordering_attr: ChannelOrderingAttribute,
     conn_id_attr_on_a: PacketConnectionIdAttribute,
+    /// The `module` attribute attached by the emitting chain, if any.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    module_attr: Option<ModuleAttribute>,
+    /// Non-standard attributes attached by the emitting chain (e.g. by a fee
+    /// middleware), preserved so that they survive a round-trip.
+    extra: ExtraAttributes,
 }
 
 impl SendPacket {
@@ -622,9 +1240,17 @@ impl SendPacket {
             chan_id_attr_on_b: packet.chan_id_on_b.into(),
             channel_ordering_attr: channel_ordering.into(),
             conn_id_attr_on_a: src_connection_id.into(),
+            extra: ExtraAttributes::default(),
+            module_attr: None,
         }
     }
 
+    /// Attaches non-standard attributes, emitted after the standard ones.
+    pub fn with_extra_attributes(mut self, extra: Vec<abci::EventAttribute>) -> Self {
+        self.extra = extra.into();
+        self
+    }
+
     pub fn packet_data(&self) -> &[u8] {
         &self.packet_data_attr.packet_data
     }
@@ -661,74 +1287,763 @@ impl SendPacket {
         &self.channel_ordering_attr.order
     }
 
+    pub fn is_ordered(&self) -> bool {
+        self.channel_ordering() == &Order::Ordered
+    }
+
+    pub fn is_unordered(&self) -> bool {
+        self.channel_ordering() == &Order::Unordered
+    }
+
     pub fn conn_id_on_a(&self) -> &ConnectionId {
         &self.conn_id_attr_on_a.connection_id
     }
 
-    pub fn event_type(&self) -> &str {
-        SEND_PACKET_EVENT
+    /// Returns the non-standard attributes carried by the event.
+    pub fn extra_attributes(&self) -> &[abci::EventAttribute] {
+        &self.extra.attributes
     }
-}
 
-impl TryFrom<SendPacket> for abci::Event {
-    type Error = ChannelError;
+    /// Compares two events on their canonical decoded values only.
+    ///
+    /// Unlike `PartialEq`, this ignores how the events were encoded, i.e.
+    /// whether the deprecated plaintext `packet_data` attribute accompanied
+    /// `packet_data_hex`, as well as any non-standard attributes.
+    pub fn eq_ignoring_deprecated(&self, other: &Self) -> bool {
+        self.packet_data_attr == other.packet_data_attr
+            && self.timeout_height_attr_on_b == other.timeout_height_attr_on_b
+            && self.timeout_timestamp_attr_on_b == other.timeout_timestamp_attr_on_b
+            && self.seq_attr_on_a == other.seq_attr_on_a
+            && self.port_id_attr_on_a == other.port_id_attr_on_a
+            && self.chan_id_attr_on_a == other.chan_id_attr_on_a
+            && self.port_id_attr_on_b == other.port_id_attr_on_b
+            && self.chan_id_attr_on_b == other.chan_id_attr_on_b
+            && self.channel_ordering_attr == other.channel_ordering_attr
+            && self.conn_id_attr_on_a == other.conn_id_attr_on_a
+    }
 
-    fn try_from(v: SendPacket) -> Result<Self, Self::Error> {
-        let mut attributes = Vec::with_capacity(11);
-        attributes.append(&mut v.packet_data_attr.try_into()?);
-        attributes.push(v.timeout_height_attr_on_b.into());
-        attributes.push(v.timeout_timestamp_attr_on_b.into());
-        attributes.push(v.seq_attr_on_a.into());
-        attributes.push(v.port_id_attr_on_a.into());
-        attributes.push(v.chan_id_attr_on_a.into());
-        attributes.push(v.port_id_attr_on_b.into());
-        attributes.push(v.chan_id_attr_on_b.into());
-        attributes.push(v.channel_ordering_attr.into());
-        attributes.push(v.conn_id_attr_on_a.into());
+    /// A SHA-256 digest of the event, for relayers deduplicating events
+    /// received from several RPC endpoints.
+    ///
+    /// The digest covers the same values as [`Self::eq_ignoring_deprecated`],
+    /// serialized as the hex-only standard attributes with every key and value
+    /// prefixed by its length. The same packet thus hashes identically whether
+    /// or not the deprecated `packet_data` attribute was emitted.
+    pub fn event_hash(&self) -> [u8; 32] {
+        use sha2::Digest;
+
+        let mut attributes = vec![self.packet_data_attr.hex_attribute()];
+        attributes.append(&mut self.non_data_attributes());
+
+        let mut hasher = sha2::Sha256::new();
+        let mut update = |field: &[u8]| {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field);
+        };
+        update(SEND_PACKET_EVENT.as_bytes());
+        for attribute in &attributes {
+            update(attribute.key_bytes());
+            update(attribute.value_bytes());
+        }
+
+        hasher.finalize().into()
+    }
+
+    /// Builds the ABCI event with the packet data only in its hex-encoded
+    /// form. Unlike `to_abci_event`, this never fails, even for events decoded
+    /// without validation, so that it can serve to identify events, e.g. to
+    /// order or deduplicate them.
+    pub fn to_hex_only_event(&self) -> abci::Event {
+        let mut attributes = vec![self.packet_data_attr.hex_attribute()];
+        attributes.append(&mut self.non_data_attributes());
+        attributes.extend(self.module_attr.clone().map(abci::EventAttribute::from));
+        attributes.extend_from_slice(&self.extra.attributes);
+
+        abci::Event {
+            kind: SEND_PACKET_EVENT.to_string(),
+            attributes,
+        }
+    }
+
+    /// Moves the timeout height from revision `from` to revision `to`, e.g.
+    /// to replay events of a testnet against a mainnet revision.
+    pub fn remap_timeout_revision(&mut self, from: u64, to: u64) {
+        self.timeout_height_attr_on_b.remap_revision(from, to);
+    }
+
+    /// Builds the ABCI event in the attribute layout of `profile`, `TryFrom`
+    /// using [`EmissionProfile::Default`]. Packet data only needs to be valid
+    /// UTF-8 if the profile emits plaintext attributes.
+    pub fn to_abci_event(self, profile: EmissionProfile) -> Result<abci::Event, ChannelError> {
+        let mut non_data_attributes = self.non_data_attributes();
+        let connection_id = profile.connection_id_attribute(&self.conn_id_attr_on_a);
+        let mut attributes = self.packet_data_attr.into_attributes(profile)?;
+        attributes.append(&mut non_data_attributes);
+        attributes.extend(connection_id);
+        attributes.extend(self.module_attr.map(abci::EventAttribute::from));
+        attributes.extend(self.extra.attributes);
 
         Ok(abci::Event {
             kind: SEND_PACKET_EVENT.to_string(),
             attributes,
         })
     }
-}
 
-#[cfg_attr(
-    feature = "parity-scale-codec",
-    derive(
-        parity_scale_codec::Encode,
-        parity_scale_codec::Decode,
-        scale_info::TypeInfo
-    )
-)]
-#[cfg_attr(
-    feature = "borsh",
-    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
-)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct ReceivePacket {
-    packet_data_attr: PacketDataAttribute,
-    timeout_height_attr_on_b: TimeoutHeightAttribute,
-    timeout_timestamp_attr_on_b: TimeoutTimestampAttribute,
-    seq_attr_on_a: SequenceAttribute,
-    port_id_attr_on_a: SrcPortIdAttribute,
-    chan_id_attr_on_a: SrcChannelIdAttribute,
-    port_id_attr_on_b: DstPortIdAttribute,
-    chan_id_attr_on_b: DstChannelIdAttribute,
-    channel_ordering_attr: ChannelOrderingAttribute,
-    conn_id_attr_on_b: PacketConnectionIdAttribute,
-}
+    /// Builds the ABCI event like `TryFrom`, but with a lossy plaintext
+    /// `packet_data` attribute for packet data that is not valid UTF-8 (see
+    /// [`PacketDataAttribute::into_lossy_attributes`]), instead of failing.
+    pub fn into_lossy_event(self) -> abci::Event {
+        let mut non_data_attributes = self.non_data_attributes();
+        let mut attributes = self.packet_data_attr.into_lossy_attributes();
+        attributes.append(&mut non_data_attributes);
+        attributes.extend(self.module_attr.map(abci::EventAttribute::from));
+        attributes.extend(self.extra.attributes);
 
-impl ReceivePacket {
-    pub fn new(packet: Packet, channel_ordering: Order, dst_connection_id: ConnectionId) -> Self {
-        Self {
-            packet_data_attr: packet.data.into(),
-            timeout_height_attr_on_b: packet.timeout_height_on_b.into(),
-            timeout_timestamp_attr_on_b: packet.timeout_timestamp_on_b.into(),
-            seq_attr_on_a: packet.seq_on_a.into(),
-            port_id_attr_on_a: packet.port_id_on_a.into(),
-            chan_id_attr_on_a: packet.chan_id_on_a.into(),
+        abci::Event {
+            kind: SEND_PACKET_EVENT.to_string(),
+            attributes,
+        }
+    }
+
+    /// Builds the ABCI event with the packet data replaced by its length and
+    /// SHA-256 hash, so that it can be logged without the raw payload.
+    pub fn redacted(&self) -> abci::Event {
+        let mut attributes = self.packet_data_attr.redacted();
+        attributes.append(&mut self.non_data_attributes());
+        attributes.extend(self.module_attr.clone().map(abci::EventAttribute::from));
+        attributes.extend_from_slice(&self.extra.attributes);
+
+        abci::Event {
+            kind: SEND_PACKET_EVENT.to_string(),
+            attributes,
+        }
+    }
+
+    /// Emits the standard attributes of the event in the exact order used by
+    /// ibc-go's `EmitSendPacketEvent`:
+    ///
+    /// `packet_data`, `packet_data_hex`, `packet_timeout_height`,
+    /// `packet_timeout_timestamp`, `packet_sequence`, `packet_src_port`,
+    /// `packet_src_channel`, `packet_dst_port`, `packet_dst_channel`,
+    /// `packet_channel_ordering`, `packet_connection`.
+    ///
+    /// This order is guaranteed to stay stable, so that events can be hashed
+    /// and compared byte-for-byte against those of ibc-go. Non-standard
+    /// attributes are left out.
+    pub fn canonical_attributes(&self) -> Result<Vec<abci::EventAttribute>, ChannelError> {
+        let mut attributes: Vec<abci::EventAttribute> = self.packet_data_attr.clone().try_into()?;
+        attributes.append(&mut self.non_data_attributes());
+        Ok(attributes)
+    }
+
+    /// Recomputes the ICS-04 commitment of the sent packet, to be checked
+    /// against the one stored by the sending chain.
+    pub fn packet_commitment(&self) -> PacketCommitment {
+        compute_packet_commitment(
+            self.packet_data(),
+            self.timeout_height_on_b(),
+            self.timeout_timestamp_on_b(),
+        )
+    }
+
+    /// The standard attributes emitted after the packet data ones.
+    fn non_data_attributes(&self) -> Vec<abci::EventAttribute> {
+        vec![
+            self.timeout_height_attr_on_b.clone().into(),
+            self.timeout_timestamp_attr_on_b.clone().into(),
+            self.seq_attr_on_a.clone().into(),
+            self.port_id_attr_on_a.to_attribute(),
+            self.chan_id_attr_on_a.to_attribute(),
+            self.port_id_attr_on_b.to_attribute(),
+            self.chan_id_attr_on_b.to_attribute(),
+            self.channel_ordering_attr.clone().into(),
+            self.conn_id_attr_on_a.to_attribute(),
+        ]
+    }
+
+    /// Attaches the name of the module emitting the event, emitted as the
+    /// `module` attribute.
+    pub fn with_module(mut self, module: String) -> Self {
+        self.module_attr = Some(module.into());
+        self
+    }
+
+    pub fn module(&self) -> Option<&str> {
+        self.module_attr.as_ref().map(|attr| attr.module.as_str())
+    }
+
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
+        SEND_PACKET_EVENT
+    }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
+}
+
+impl TryFrom<SendPacket> for abci::Event {
+    type Error = ChannelError;
+
+    fn try_from(v: SendPacket) -> Result<Self, Self::Error> {
+        v.to_abci_event(EmissionProfile::Default)
+    }
+}
+
+impl TryFrom<abci::Event> for SendPacket {
+    type Error = ChannelError;
+
+    fn try_from(event: abci::Event) -> Result<Self, Self::Error> {
+        let attrs = PacketEventAttributes::parse(&event, SEND_PACKET_EVENT)?;
+
+        Ok(Self {
+            packet_data_attr: required_attribute(attrs.packet_data, PKT_DATA_HEX_ATTRIBUTE_KEY)?,
+            timeout_height_attr_on_b: required_attribute(
+                attrs.timeout_height,
+                PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY,
+            )?,
+            timeout_timestamp_attr_on_b: required_attribute(
+                attrs.timeout_timestamp,
+                PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+            )?,
+            seq_attr_on_a: required_attribute(attrs.sequence, PKT_SEQ_ATTRIBUTE_KEY)?,
+            port_id_attr_on_a: required_attribute(attrs.src_port_id, PKT_SRC_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_a: required_attribute(
+                attrs.src_channel_id,
+                PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            port_id_attr_on_b: required_attribute(attrs.dst_port_id, PKT_DST_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_b: required_attribute(
+                attrs.dst_channel_id,
+                PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            channel_ordering_attr: required_attribute(
+                attrs.channel_ordering,
+                PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+            )?,
+            conn_id_attr_on_a: required_attribute(
+                attrs.connection_id,
+                PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+            )?,
+            module_attr: attrs.module,
+            extra: attrs.extra.into(),
+        })
+    }
+}
+
+/// The fields of a `SendPacket` event that could be parsed, as returned by
+/// [`parse_lenient`] for indexers that would rather keep a partial event than
+/// drop it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartialSendPacket {
+    pub packet_data: Option<Vec<u8>>,
+    pub timeout_height_on_b: Option<TimeoutHeight>,
+    pub timeout_timestamp_on_b: Option<Timestamp>,
+    pub seq_on_a: Option<Sequence>,
+    pub port_id_on_a: Option<PortId>,
+    pub chan_id_on_a: Option<ChannelId>,
+    pub port_id_on_b: Option<PortId>,
+    pub chan_id_on_b: Option<ChannelId>,
+    pub channel_ordering: Option<Order>,
+    pub conn_id_on_a: Option<ConnectionId>,
+    pub module: Option<String>,
+    pub extra: Vec<abci::EventAttribute>,
+}
+
+/// Parses as much of a `SendPacket` event as possible, returning the errors
+/// of the attributes that could not be parsed alongside the parsed ones.
+///
+/// Missing attributes are reported as [`ChannelError::MissingAttributeKey`],
+/// unless a malformed attribute with the same key was already reported. Use
+/// `SendPacket::try_from` to parse the event all-or-nothing instead.
+pub fn parse_lenient(event: &abci::Event) -> (PartialSendPacket, Vec<ChannelError>) {
+    let (attrs, mut errors) = PacketEventAttributes::parse_lenient(event, SEND_PACKET_EVENT);
+    if canonical_event_kind(&event.kind) == SEND_PACKET_EVENT {
+        let present: BTreeSet<String> = event
+            .attributes
+            .iter()
+            .filter_map(|attr| attr.key_str().ok())
+            .map(ToString::to_string)
+            .collect();
+        let missing = [
+            (attrs.packet_data.is_none(), PKT_DATA_HEX_ATTRIBUTE_KEY),
+            (
+                attrs.timeout_height.is_none(),
+                PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY,
+            ),
+            (
+                attrs.timeout_timestamp.is_none(),
+                PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+            ),
+            (attrs.sequence.is_none(), PKT_SEQ_ATTRIBUTE_KEY),
+            (attrs.src_port_id.is_none(), PKT_SRC_PORT_ATTRIBUTE_KEY),
+            (
+                attrs.src_channel_id.is_none(),
+                PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+            ),
+            (attrs.dst_port_id.is_none(), PKT_DST_PORT_ATTRIBUTE_KEY),
+            (
+                attrs.dst_channel_id.is_none(),
+                PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+            ),
+            (
+                attrs.channel_ordering.is_none(),
+                PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+            ),
+            (
+                attrs.connection_id.is_none(),
+                PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+            ),
+        ];
+        errors.extend(
+            missing
+                .into_iter()
+                .filter(|(is_missing, key)| *is_missing && !present.contains(*key))
+                .map(|(_, key)| missing_attribute_key(key)),
+        );
+    }
+
+    let partial = PartialSendPacket {
+        packet_data: attrs.packet_data.map(|attr| attr.packet_data),
+        timeout_height_on_b: attrs.timeout_height.map(|attr| attr.timeout_height),
+        timeout_timestamp_on_b: attrs.timeout_timestamp.map(|attr| attr.timeout_timestamp),
+        seq_on_a: attrs.sequence.map(|attr| attr.sequence),
+        port_id_on_a: attrs.src_port_id.map(|attr| attr.src_port_id),
+        chan_id_on_a: attrs.src_channel_id.map(|attr| attr.src_channel_id),
+        port_id_on_b: attrs.dst_port_id.map(|attr| attr.dst_port_id),
+        chan_id_on_b: attrs.dst_channel_id.map(|attr| attr.dst_channel_id),
+        channel_ordering: attrs.channel_ordering.map(|attr| attr.order),
+        conn_id_on_a: attrs.connection_id.map(|attr| attr.connection_id),
+        module: attrs.module.map(|attr| attr.module),
+        extra: attrs.extra,
+    };
+
+    (partial, errors)
+}
+
+/// Reassembles the packet committed by a `SendPacket` event, e.g. for a
+/// relayer to submit it on the destination chain.
+///
+/// Fails with [`PacketError::ZeroPacketData`] if the event carries no packet
+/// data, as such a packet could never have been sent.
+impl TryFrom<SendPacket> for Packet {
+    type Error = PacketError;
+
+    fn try_from(v: SendPacket) -> Result<Self, Self::Error> {
+        if v.packet_data_attr.packet_data.is_empty() {
+            return Err(PacketError::ZeroPacketData);
+        }
+
+        Ok(Packet {
+            seq_on_a: v.seq_attr_on_a.sequence,
+            port_id_on_a: v.port_id_attr_on_a.src_port_id,
+            chan_id_on_a: v.chan_id_attr_on_a.src_channel_id,
+            port_id_on_b: v.port_id_attr_on_b.dst_port_id,
+            chan_id_on_b: v.chan_id_attr_on_b.dst_channel_id,
+            data: v.packet_data_attr.packet_data,
+            timeout_height_on_b: v.timeout_height_attr_on_b.timeout_height,
+            timeout_timestamp_on_b: v.timeout_timestamp_attr_on_b.timeout_timestamp,
+        })
+    }
+}
+
+/// A `SendPacket` event along with the height of the block that emitted it,
+/// which the event itself does not carry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SendPacketAtHeight {
+    event: SendPacket,
+    height: Height,
+}
+
+impl SendPacketAtHeight {
+    pub fn new(event: SendPacket, height: Height) -> Self {
+        Self { event, height }
+    }
+
+    /// Parses a `send_packet` event emitted by the block at `height`.
+    pub fn parse(event: abci::Event, height: Height) -> Result<Self, ChannelError> {
+        Ok(Self::new(event.try_into()?, height))
+    }
+
+    pub fn event(&self) -> &SendPacket {
+        &self.event
+    }
+
+    /// The height of the source chain block that emitted the event.
+    pub fn height(&self) -> Height {
+        self.height
+    }
+
+    /// The minimum height the client of the source chain on the destination
+    /// chain must be updated to before the packet can be relayed.
+    ///
+    /// The packet commitment is written to the state of the block that emitted
+    /// the event, whose app hash is only committed to by the header of the
+    /// next block.
+    pub fn min_client_height(&self) -> Height {
+        self.height.increment()
+    }
+
+    pub fn into_event(self) -> SendPacket {
+        self.event
+    }
+}
+
+/// Checks that all the hex-encoded attributes of an event, i.e.
+/// `packet_data_hex` and `packet_ack_hex`, use the same letter case.
+///
+/// Valid hex decodes regardless of its case, so this is only a soft integrity
+/// check: mixed casing hints at a buggy emitter. Values made of digits only
+/// are compatible with either case.
+pub fn validate_encoding_consistency(attrs: &[abci::EventAttribute]) -> Result<(), ChannelError> {
+    let mut expected_uppercase = None;
+
+    for attr in attrs {
+        let Ok(key) = attr.key_str() else {
+            continue;
+        };
+        if key != PKT_DATA_HEX_ATTRIBUTE_KEY && key != PKT_ACK_HEX_ATTRIBUTE_KEY {
+            continue;
+        }
+
+        let value = attr.value_bytes();
+        let has_upper = value.iter().any(u8::is_ascii_uppercase);
+        let has_lower = value.iter().any(u8::is_ascii_lowercase);
+
+        let uppercase = match (has_upper, has_lower) {
+            (false, false) => continue,
+            (true, false) => true,
+            (false, true) => false,
+            (true, true) => {
+                return Err(ChannelError::InconsistentHexCasing {
+                    attribute_key: key.to_string(),
+                })
+            }
+        };
+
+        if *expected_uppercase.get_or_insert(uppercase) != uppercase {
+            return Err(ChannelError::InconsistentHexCasing {
+                attribute_key: key.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Audits that each event, paired with the `msg_index` it was tagged with, is
+/// attributable to one of the `num_msgs` messages of the transaction that
+/// produced it.
+pub fn validate_event_attribution(
+    events: &[(abci::Event, Option<u32>)],
+    num_msgs: usize,
+) -> Result<(), ChannelError> {
+    for (position, (event, msg_index)) in events.iter().enumerate() {
+        let Some(msg_index) = *msg_index else {
+            return Err(ChannelError::OrphanedEvent {
+                event_kind: event.kind.clone(),
+                position,
+            });
+        };
+
+        if usize::try_from(msg_index).map_or(true, |index| index >= num_msgs) {
+            return Err(ChannelError::EventMsgIndexOutOfRange {
+                event_kind: event.kind.clone(),
+                msg_index,
+                num_msgs,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Lazily parses the `SendPacket` events of a block.
+///
+/// Events of any other kind are skipped, while a malformed `send_packet` event
+/// yields an error without interrupting the iteration.
+pub fn iter_send_packets(
+    events: &[abci::Event],
+) -> impl Iterator<Item = Result<SendPacket, ChannelError>> + '_ {
+    events
+        .iter()
+        .filter(|event| event.kind == SEND_PACKET_EVENT)
+        .map(|event| SendPacket::try_from(event.clone()))
+}
+
+/// The differences between the `SendPacket` events of the same block as seen
+/// by two nodes, as computed by [`reconcile`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PacketDiff {
+    /// Packets only reported by the first node.
+    pub only_in_a: Vec<SendPacket>,
+    /// Packets only reported by the second node.
+    pub only_in_b: Vec<SendPacket>,
+    /// Packets reported by both nodes under the same source channel and
+    /// sequence, but with different contents, as `(a, b)` pairs.
+    pub conflicting: Vec<(SendPacket, SendPacket)>,
+}
+
+impl PacketDiff {
+    /// Tells whether both nodes reported the same packets.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.conflicting.is_empty()
+    }
+}
+
+/// Compares the `SendPacket` events reported by two nodes for the same block,
+/// matching packets by source channel and sequence.
+///
+/// Events of other kinds and malformed `send_packet` events are ignored. Each
+/// list of the returned diff is ordered by source channel and sequence.
+pub fn reconcile(a: &[abci::Event], b: &[abci::Event]) -> PacketDiff {
+    fn by_key(events: &[abci::Event]) -> BTreeMap<(ChannelId, Sequence), SendPacket> {
+        iter_send_packets(events)
+            .filter_map(Result::ok)
+            .map(|packet| ((packet.chan_id_on_a().clone(), *packet.seq_on_a()), packet))
+            .collect()
+    }
+
+    let mut packets_in_b = by_key(b);
+    let mut diff = PacketDiff::default();
+
+    for (key, packet_a) in by_key(a) {
+        match packets_in_b.remove(&key) {
+            Some(packet_b) if packet_b != packet_a => diff.conflicting.push((packet_a, packet_b)),
+            Some(_) => {}
+            None => diff.only_in_a.push(packet_a),
+        }
+    }
+    diff.only_in_b = packets_in_b.into_values().collect();
+
+    diff
+}
+
+/// Converts a batch of `SendPacket` events into ABCI events, each with the
+/// `send_packet` kind and the attributes in their canonical order.
+///
+/// Stops at the first event that cannot be converted, reporting its position
+/// in the batch through [`ChannelError::EventConversionFailed`].
+pub fn to_abci_events(
+    packets: impl IntoIterator<Item = SendPacket>,
+) -> Result<Vec<abci::Event>, ChannelError> {
+    packets
+        .into_iter()
+        .enumerate()
+        .map(|(index, packet)| {
+            abci::Event::try_from(packet).map_err(|e| ChannelError::EventConversionFailed {
+                index,
+                error: Box::new(e),
+            })
+        })
+        .collect()
+}
+
+/// Checks that a `SendPacket` event and its matching `ReceivePacket` event
+/// report the same channel ordering.
+///
+/// Both ends of a channel share one ordering, so a disagreement hints at an
+/// inconsistency between the source and destination chains.
+pub fn validate_ordering_consistency(
+    send: &SendPacket,
+    recv: &ReceivePacket,
+) -> Result<(), ChannelError> {
+    if send.channel_ordering() != recv.channel_ordering() {
+        return Err(ChannelError::OrderingMismatch {
+            send_ordering: *send.channel_ordering(),
+            recv_ordering: *recv.channel_ordering(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that at least one of the timeouts of a packet is set, failing with
+/// [`ChannelError::MissingTimeout`] otherwise.
+///
+/// A packet that can never time out cannot produce a `timeout_packet` event,
+/// so such an event is rejected when parsed.
+pub fn validate_timeout_present(
+    timeout_height: &TimeoutHeight,
+    timeout_timestamp: &Timestamp,
+) -> Result<(), ChannelError> {
+    if !timeout_height.is_set() && !timeout_timestamp.is_set() {
+        return Err(ChannelError::MissingTimeout);
+    }
+
+    Ok(())
+}
+
+/// The attributes identifying a packet, which nodes are expected to index.
+const KEY_PACKET_ATTRIBUTES: [&str; 3] = [
+    PKT_SEQ_ATTRIBUTE_KEY,
+    PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+    PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+];
+
+/// Checks that the key attributes of a packet event, i.e. its sequence and
+/// channels, are flagged as indexed, failing with
+/// [`ChannelError::NonIndexedAttribute`] otherwise.
+///
+/// Attributes that are absent are left to the regular parsers to report.
+pub fn validate_key_attributes_indexed(event: &abci::Event) -> Result<(), ChannelError> {
+    for attribute in &event.attributes {
+        let Ok(key) = attribute.key_str() else {
+            continue;
+        };
+        let is_key_attribute = KEY_PACKET_ATTRIBUTES
+            .iter()
+            .any(|expected| attribute_key_matches(key, expected));
+        if is_key_attribute && !attribute.index() {
+            return Err(ChannelError::NonIndexedAttribute {
+                attribute_key: key.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Flags the key attributes of a packet event, i.e. its sequence and
+/// channels, as indexed, for chains whose consumers parse events with
+/// `try_from_indexed`. Other attributes keep their flag.
+///
+/// Events are otherwise emitted with every attribute non-indexed, leaving the
+/// choice of attributes to index to the node configuration.
+pub fn index_key_attributes(mut event: abci::Event) -> abci::Event {
+    for attribute in &mut event.attributes {
+        let Ok(key) = attribute.key_str() else {
+            continue;
+        };
+        if KEY_PACKET_ATTRIBUTES
+            .iter()
+            .any(|expected| attribute_key_matches(key, expected))
+        {
+            attribute.set_index(true);
+        }
+    }
+
+    event
+}
+
+/// Implements `try_from_indexed`, a strict variant of `TryFrom<abci::Event>`
+/// for packet events.
+macro_rules! impl_try_from_indexed {
+    ($($event:ty),+ $(,)?) => {
+        $(
+            impl $event {
+                /// Parses the event like `TryFrom<abci::Event>`, additionally
+                /// requiring its key attributes to be flagged as indexed (see
+                /// [`validate_key_attributes_indexed`]).
+                pub fn try_from_indexed(event: abci::Event) -> Result<Self, ChannelError> {
+                    validate_key_attributes_indexed(&event)?;
+                    Self::try_from(event)
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from_indexed!(
+    SendPacket,
+    ReceivePacket,
+    WriteAcknowledgement,
+    AcknowledgePacket,
+    TimeoutPacket,
+);
+
+/// Checks that the timeouts of a packet both refer to its destination chain,
+/// whose latest height and time are `dst_height` and `dst_time`: at least one
+/// of the timeouts must be set, a timeout height must be on the revision of
+/// `dst_height`, and neither timeout may have elapsed yet, see
+/// [`PacketTimeout::is_elapsed`].
+///
+/// Timeouts that disagree with the destination chain hint at a packet whose
+/// fields were cross-wired with those of another chain.
+pub fn validate_timeout_coherence(
+    timeout_height: &TimeoutHeight,
+    timeout_timestamp: &Timestamp,
+    dst_height: Height,
+    dst_time: Timestamp,
+) -> Result<(), ChannelError> {
+    validate_timeout_present(timeout_height, timeout_timestamp)?;
+
+    if let TimeoutHeight::At(height) = timeout_height {
+        if height.revision_number() != dst_height.revision_number() {
+            return Err(ChannelError::TimeoutRevisionMismatch {
+                timeout_height: *timeout_height,
+                dst_revision: dst_height.revision_number(),
+            });
+        }
+    }
+
+    if PacketTimeout::new(*timeout_height, *timeout_timestamp).is_elapsed(dst_height, dst_time) {
+        return Err(ChannelError::ElapsedTimeout {
+            timeout_height: *timeout_height,
+            timeout_timestamp: *timeout_timestamp,
+            dst_height,
+            dst_time,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that the sequence of a `SendPacket` event does not exceed
+/// `max_expected`, e.g. the last sequence sent according to the channel's
+/// `next_sequence_send`.
+///
+/// A sequence beyond the expected range hints at a spoofed or corrupt event.
+pub fn validate_sequence_range(
+    event: &SendPacket,
+    max_expected: Sequence,
+) -> Result<(), ChannelError> {
+    if *event.seq_on_a() > max_expected {
+        return Err(ChannelError::SequenceOutOfRange {
+            sequence: *event.seq_on_a(),
+            max_expected,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceivePacket {
+    packet_data_attr: PacketDataAttribute,
+    timeout_height_attr_on_b: TimeoutHeightAttribute,
+    timeout_timestamp_attr_on_b: TimeoutTimestampAttribute,
+    seq_attr_on_a: SequenceAttribute,
+    port_id_attr_on_a: SrcPortIdAttribute,
+    chan_id_attr_on_a: SrcChannelIdAttribute,
+    port_id_attr_on_b: DstPortIdAttribute,
+    chan_id_attr_on_b: DstChannelIdAttribute,
+    channel_ordering_attr: ChannelOrderingAttribute,
+    conn_id_attr_on_b: PacketConnectionIdAttribute,
+}
+
+impl ReceivePacket {
+    pub fn new(packet: Packet, channel_ordering: Order, dst_connection_id: ConnectionId) -> Self {
+        Self {
+            packet_data_attr: packet.data.into(),
+            timeout_height_attr_on_b: packet.timeout_height_on_b.into(),
+            timeout_timestamp_attr_on_b: packet.timeout_timestamp_on_b.into(),
+            seq_attr_on_a: packet.seq_on_a.into(),
+            port_id_attr_on_a: packet.port_id_on_a.into(),
+            chan_id_attr_on_a: packet.chan_id_on_a.into(),
             port_id_attr_on_b: packet.port_id_on_b.into(),
             chan_id_attr_on_b: packet.chan_id_on_b.into(),
             channel_ordering_attr: channel_ordering.into(),
@@ -772,12 +2087,75 @@ impl ReceivePacket {
         &self.channel_ordering_attr.order
     }
 
+    pub fn is_ordered(&self) -> bool {
+        self.channel_ordering() == &Order::Ordered
+    }
+
+    pub fn is_unordered(&self) -> bool {
+        self.channel_ordering() == &Order::Unordered
+    }
+
     pub fn conn_id_on_a(&self) -> &ConnectionId {
         &self.conn_id_attr_on_b.connection_id
     }
 
+    /// Builds the ABCI event in the attribute layout of `profile`, `TryFrom`
+    /// using [`EmissionProfile::Default`].
+    pub fn to_abci_event(self, profile: EmissionProfile) -> Result<abci::Event, ChannelError> {
+        let connection_id = profile.connection_id_attribute(&self.conn_id_attr_on_b);
+        let mut attributes = AttributeMap::from(self.packet_data_attr.into_attributes(profile)?);
+        attributes.insert(self.timeout_height_attr_on_b)?;
+        attributes.insert(self.timeout_timestamp_attr_on_b)?;
+        attributes.insert(self.seq_attr_on_a)?;
+        attributes.insert(self.port_id_attr_on_a)?;
+        attributes.insert(self.chan_id_attr_on_a)?;
+        attributes.insert(self.port_id_attr_on_b)?;
+        attributes.insert(self.chan_id_attr_on_b)?;
+        attributes.insert(self.channel_ordering_attr)?;
+        attributes.insert(self.conn_id_attr_on_b)?;
+        attributes.extend(connection_id.into_iter().collect());
+
+        Ok(abci::Event {
+            kind: RECV_PACKET_EVENT.to_string(),
+            attributes: attributes.into_attributes(),
+        })
+    }
+
+    /// Builds the ABCI event with the packet data only in its hex-encoded
+    /// form. Unlike `to_abci_event`, this never fails, even for events decoded
+    /// without validation, so that it can serve to identify events, e.g. to
+    /// order or deduplicate them.
+    pub fn to_hex_only_event(&self) -> abci::Event {
+        abci::Event {
+            kind: RECV_PACKET_EVENT.to_string(),
+            attributes: vec![
+                self.packet_data_attr.hex_attribute(),
+                self.timeout_height_attr_on_b.clone().into(),
+                self.timeout_timestamp_attr_on_b.clone().into(),
+                self.seq_attr_on_a.clone().into(),
+                self.port_id_attr_on_a.to_attribute(),
+                self.chan_id_attr_on_a.to_attribute(),
+                self.port_id_attr_on_b.to_attribute(),
+                self.chan_id_attr_on_b.to_attribute(),
+                self.channel_ordering_attr.clone().into(),
+                self.conn_id_attr_on_b.to_attribute(),
+            ],
+        }
+    }
+
+    /// Moves the timeout height from revision `from` to revision `to`, e.g.
+    /// to replay events of a testnet against a mainnet revision.
+    pub fn remap_timeout_revision(&mut self, from: u64, to: u64) {
+        self.timeout_height_attr_on_b.remap_revision(from, to);
+    }
+
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
+        RECV_PACKET_EVENT
+    }
+
     pub fn event_type(&self) -> &str {
-        RECEIVE_PACKET_EVENT
+        Self::kind()
     }
 }
 
@@ -785,21 +2163,45 @@ impl TryFrom<ReceivePacket> for abci::Event {
     type Error = ChannelError;
 
     fn try_from(v: ReceivePacket) -> Result<Self, Self::Error> {
-        let mut attributes = Vec::with_capacity(11);
-        attributes.append(&mut v.packet_data_attr.try_into()?);
-        attributes.push(v.timeout_height_attr_on_b.into());
-        attributes.push(v.timeout_timestamp_attr_on_b.into());
-        attributes.push(v.seq_attr_on_a.into());
-        attributes.push(v.port_id_attr_on_a.into());
-        attributes.push(v.chan_id_attr_on_a.into());
-        attributes.push(v.port_id_attr_on_b.into());
-        attributes.push(v.chan_id_attr_on_b.into());
-        attributes.push(v.channel_ordering_attr.into());
-        attributes.push(v.conn_id_attr_on_b.into());
+        v.to_abci_event(EmissionProfile::Default)
+    }
+}
 
-        Ok(abci::Event {
-            kind: RECEIVE_PACKET_EVENT.to_string(),
-            attributes,
+impl TryFrom<abci::Event> for ReceivePacket {
+    type Error = ChannelError;
+
+    fn try_from(event: abci::Event) -> Result<Self, Self::Error> {
+        let attrs = PacketEventAttributes::parse(&event, RECV_PACKET_EVENT)?;
+
+        Ok(Self {
+            packet_data_attr: required_attribute(attrs.packet_data, PKT_DATA_HEX_ATTRIBUTE_KEY)?,
+            timeout_height_attr_on_b: required_attribute(
+                attrs.timeout_height,
+                PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY,
+            )?,
+            timeout_timestamp_attr_on_b: required_attribute(
+                attrs.timeout_timestamp,
+                PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+            )?,
+            seq_attr_on_a: required_attribute(attrs.sequence, PKT_SEQ_ATTRIBUTE_KEY)?,
+            port_id_attr_on_a: required_attribute(attrs.src_port_id, PKT_SRC_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_a: required_attribute(
+                attrs.src_channel_id,
+                PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            port_id_attr_on_b: required_attribute(attrs.dst_port_id, PKT_DST_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_b: required_attribute(
+                attrs.dst_channel_id,
+                PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            channel_ordering_attr: required_attribute(
+                attrs.channel_ordering,
+                PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+            )?,
+            conn_id_attr_on_b: required_attribute(
+                attrs.connection_id,
+                PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+            )?,
         })
     }
 }
@@ -891,30 +2293,107 @@ impl WriteAcknowledgement {
         &self.conn_id_attr_on_b.connection_id
     }
 
-    pub fn event_type(&self) -> &str {
+    /// Builds the ABCI event in the attribute layout of `profile`, `TryFrom`
+    /// using [`EmissionProfile::Default`]. The packet data and the
+    /// acknowledgement follow the same profile.
+    pub fn to_abci_event(self, profile: EmissionProfile) -> Result<abci::Event, ChannelError> {
+        let connection_id = profile.connection_id_attribute(&self.conn_id_attr_on_b);
+        let mut attributes = AttributeMap::from(self.packet_data.into_attributes(profile)?);
+        attributes.insert(self.timeout_height_attr_on_b)?;
+        attributes.insert(self.timeout_timestamp_attr_on_b)?;
+        attributes.insert(self.seq_attr_on_a)?;
+        attributes.insert(self.port_id_attr_on_a)?;
+        attributes.insert(self.chan_id_attr_on_a)?;
+        attributes.insert(self.port_id_attr_on_b)?;
+        attributes.insert(self.chan_id_attr_on_b)?;
+        attributes.extend(self.acknowledgement.into_attributes(profile)?);
+        attributes.insert(self.conn_id_attr_on_b)?;
+        attributes.extend(connection_id.into_iter().collect());
+
+        Ok(abci::Event {
+            kind: WRITE_ACK_EVENT.to_string(),
+            attributes: attributes.into_attributes(),
+        })
+    }
+
+    /// Builds the ABCI event with the packet data and the acknowledgement only
+    /// in their hex-encoded form. Unlike `to_abci_event`, this never fails,
+    /// even for events decoded without validation, so that it can serve to
+    /// identify events, e.g. to order or deduplicate them.
+    pub fn to_hex_only_event(&self) -> abci::Event {
+        abci::Event {
+            kind: WRITE_ACK_EVENT.to_string(),
+            attributes: vec![
+                self.packet_data.hex_attribute(),
+                self.timeout_height_attr_on_b.clone().into(),
+                self.timeout_timestamp_attr_on_b.clone().into(),
+                self.seq_attr_on_a.clone().into(),
+                self.port_id_attr_on_a.to_attribute(),
+                self.chan_id_attr_on_a.to_attribute(),
+                self.port_id_attr_on_b.to_attribute(),
+                self.chan_id_attr_on_b.to_attribute(),
+                self.acknowledgement.hex_attribute(),
+                self.conn_id_attr_on_b.to_attribute(),
+            ],
+        }
+    }
+
+    /// Moves the timeout height from revision `from` to revision `to`, e.g.
+    /// to replay events of a testnet against a mainnet revision.
+    pub fn remap_timeout_revision(&mut self, from: u64, to: u64) {
+        self.timeout_height_attr_on_b.remap_revision(from, to);
+    }
+
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
         WRITE_ACK_EVENT
     }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
 }
 
 impl TryFrom<WriteAcknowledgement> for abci::Event {
     type Error = ChannelError;
 
     fn try_from(v: WriteAcknowledgement) -> Result<Self, Self::Error> {
-        let mut attributes = Vec::with_capacity(11);
-        attributes.append(&mut v.packet_data.try_into()?);
-        attributes.push(v.timeout_height_attr_on_b.into());
-        attributes.push(v.timeout_timestamp_attr_on_b.into());
-        attributes.push(v.seq_attr_on_a.into());
-        attributes.push(v.port_id_attr_on_a.into());
-        attributes.push(v.chan_id_attr_on_a.into());
-        attributes.push(v.port_id_attr_on_b.into());
-        attributes.push(v.chan_id_attr_on_b.into());
-        attributes.append(&mut v.acknowledgement.try_into()?);
-        attributes.push(v.conn_id_attr_on_b.into());
+        v.to_abci_event(EmissionProfile::Default)
+    }
+}
 
-        Ok(abci::Event {
-            kind: WRITE_ACK_EVENT.to_string(),
-            attributes,
+impl TryFrom<abci::Event> for WriteAcknowledgement {
+    type Error = ChannelError;
+
+    fn try_from(event: abci::Event) -> Result<Self, Self::Error> {
+        let attrs = PacketEventAttributes::parse(&event, WRITE_ACK_EVENT)?;
+
+        Ok(Self {
+            packet_data: required_attribute(attrs.packet_data, PKT_DATA_HEX_ATTRIBUTE_KEY)?,
+            timeout_height_attr_on_b: required_attribute(
+                attrs.timeout_height,
+                PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY,
+            )?,
+            timeout_timestamp_attr_on_b: required_attribute(
+                attrs.timeout_timestamp,
+                PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+            )?,
+            seq_attr_on_a: required_attribute(attrs.sequence, PKT_SEQ_ATTRIBUTE_KEY)?,
+            port_id_attr_on_a: required_attribute(attrs.src_port_id, PKT_SRC_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_a: required_attribute(
+                attrs.src_channel_id,
+                PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            port_id_attr_on_b: required_attribute(attrs.dst_port_id, PKT_DST_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_b: required_attribute(
+                attrs.dst_channel_id,
+                PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            acknowledgement: required_attribute(attrs.acknowledgement, PKT_ACK_HEX_ATTRIBUTE_KEY)?,
+            conn_id_attr_on_b: required_attribute(
+                attrs.connection_id,
+                PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+            )?,
         })
     }
 }
@@ -992,37 +2471,123 @@ impl AcknowledgePacket {
         &self.channel_ordering_attr.order
     }
 
-    pub fn conn_id_on_a(&self) -> &ConnectionId {
-        &self.conn_id_attr_on_a.connection_id
+    pub fn is_ordered(&self) -> bool {
+        self.channel_ordering() == &Order::Ordered
     }
 
-    pub fn event_type(&self) -> &str {
-        ACK_PACKET_EVENT
+    pub fn is_unordered(&self) -> bool {
+        self.channel_ordering() == &Order::Unordered
     }
-}
 
-impl TryFrom<AcknowledgePacket> for abci::Event {
-    type Error = ChannelError;
+    pub fn conn_id_on_a(&self) -> &ConnectionId {
+        &self.conn_id_attr_on_a.connection_id
+    }
+
+    /// Builds the ABCI event in the attribute layout of `profile`, `TryFrom`
+    /// using [`EmissionProfile::Default`].
+    pub fn to_abci_event(self, profile: EmissionProfile) -> Result<abci::Event, ChannelError> {
+        let connection_id = profile.connection_id_attribute(&self.conn_id_attr_on_a);
+        let mut attributes = AttributeMap::new();
+        attributes.insert(self.timeout_height_attr_on_b)?;
+        attributes.insert(self.timeout_timestamp_attr_on_b)?;
+        attributes.insert(self.seq_on_a)?;
+        attributes.insert(self.port_id_attr_on_a)?;
+        attributes.insert(self.chan_id_attr_on_a)?;
+        attributes.insert(self.port_id_attr_on_b)?;
+        attributes.insert(self.chan_id_attr_on_b)?;
+        attributes.insert(self.channel_ordering_attr)?;
+        attributes.insert(self.conn_id_attr_on_a)?;
+        attributes.extend(connection_id.into_iter().collect());
 
-    fn try_from(v: AcknowledgePacket) -> Result<Self, Self::Error> {
         Ok(abci::Event {
+            kind: ACK_PACKET_EVENT.to_string(),
+            attributes: attributes.into_attributes(),
+        })
+    }
+
+    /// Builds the ABCI event like `to_abci_event`, but infallibly, so that it
+    /// can serve to identify events, e.g. to order or deduplicate them. The
+    /// event carries no packet data, so this is also its default layout.
+    pub fn to_hex_only_event(&self) -> abci::Event {
+        abci::Event {
             kind: ACK_PACKET_EVENT.to_string(),
             attributes: vec![
-                v.timeout_height_attr_on_b.into(),
-                v.timeout_timestamp_attr_on_b.into(),
-                v.seq_on_a.into(),
-                v.port_id_attr_on_a.into(),
-                v.chan_id_attr_on_a.into(),
-                v.port_id_attr_on_b.into(),
-                v.chan_id_attr_on_b.into(),
-                v.channel_ordering_attr.into(),
-                v.conn_id_attr_on_a.into(),
+                self.timeout_height_attr_on_b.clone().into(),
+                self.timeout_timestamp_attr_on_b.clone().into(),
+                self.seq_on_a.clone().into(),
+                self.port_id_attr_on_a.to_attribute(),
+                self.chan_id_attr_on_a.to_attribute(),
+                self.port_id_attr_on_b.to_attribute(),
+                self.chan_id_attr_on_b.to_attribute(),
+                self.channel_ordering_attr.clone().into(),
+                self.conn_id_attr_on_a.to_attribute(),
             ],
-        })
+        }
     }
-}
 
-#[cfg_attr(
+    /// Moves the timeout height from revision `from` to revision `to`, e.g.
+    /// to replay events of a testnet against a mainnet revision.
+    pub fn remap_timeout_revision(&mut self, from: u64, to: u64) {
+        self.timeout_height_attr_on_b.remap_revision(from, to);
+    }
+
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
+        ACK_PACKET_EVENT
+    }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
+}
+
+impl TryFrom<AcknowledgePacket> for abci::Event {
+    type Error = ChannelError;
+
+    fn try_from(v: AcknowledgePacket) -> Result<Self, Self::Error> {
+        v.to_abci_event(EmissionProfile::Default)
+    }
+}
+
+impl TryFrom<abci::Event> for AcknowledgePacket {
+    type Error = ChannelError;
+
+    fn try_from(event: abci::Event) -> Result<Self, Self::Error> {
+        let attrs = PacketEventAttributes::parse(&event, ACK_PACKET_EVENT)?;
+
+        Ok(Self {
+            timeout_height_attr_on_b: required_attribute(
+                attrs.timeout_height,
+                PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY,
+            )?,
+            timeout_timestamp_attr_on_b: required_attribute(
+                attrs.timeout_timestamp,
+                PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+            )?,
+            seq_on_a: required_attribute(attrs.sequence, PKT_SEQ_ATTRIBUTE_KEY)?,
+            port_id_attr_on_a: required_attribute(attrs.src_port_id, PKT_SRC_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_a: required_attribute(
+                attrs.src_channel_id,
+                PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            port_id_attr_on_b: required_attribute(attrs.dst_port_id, PKT_DST_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_b: required_attribute(
+                attrs.dst_channel_id,
+                PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            channel_ordering_attr: required_attribute(
+                attrs.channel_ordering,
+                PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+            )?,
+            conn_id_attr_on_a: required_attribute(
+                attrs.connection_id,
+                PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+            )?,
+        })
+    }
+}
+
+#[cfg_attr(
     feature = "parity-scale-codec",
     derive(
         parity_scale_codec::Encode,
@@ -1093,36 +2658,189 @@ impl TimeoutPacket {
         &self.channel_ordering_attr.order
     }
 
-    pub fn event_type(&self) -> &str {
+    pub fn is_ordered(&self) -> bool {
+        self.channel_ordering() == &Order::Ordered
+    }
+
+    pub fn is_unordered(&self) -> bool {
+        self.channel_ordering() == &Order::Unordered
+    }
+
+    /// Builds the ABCI event in the attribute layout of `profile`, `TryFrom`
+    /// using [`EmissionProfile::Default`]. Timeout events carry neither
+    /// packet data nor a connection, so every profile emits the same layout.
+    pub fn to_abci_event(self, _profile: EmissionProfile) -> Result<abci::Event, ChannelError> {
+        let mut attributes = AttributeMap::new();
+        attributes.insert(self.timeout_height_attr_on_b)?;
+        attributes.insert(self.timeout_timestamp_attr_on_b)?;
+        attributes.insert(self.seq_attr_on_a)?;
+        attributes.insert(self.port_id_attr_on_a)?;
+        attributes.insert(self.chan_id_attr_on_a)?;
+        attributes.insert(self.port_id_attr_on_b)?;
+        attributes.insert(self.chan_id_attr_on_b)?;
+        attributes.insert(self.channel_ordering_attr)?;
+
+        Ok(abci::Event {
+            kind: TIMEOUT_EVENT.to_string(),
+            attributes: attributes.into_attributes(),
+        })
+    }
+
+    /// Builds the ABCI event like `to_abci_event`, but infallibly, so that it
+    /// can serve to identify events, e.g. to order or deduplicate them. The
+    /// event carries no packet data, so this is also its default layout.
+    pub fn to_hex_only_event(&self) -> abci::Event {
+        abci::Event {
+            kind: TIMEOUT_EVENT.to_string(),
+            attributes: vec![
+                self.timeout_height_attr_on_b.clone().into(),
+                self.timeout_timestamp_attr_on_b.clone().into(),
+                self.seq_attr_on_a.clone().into(),
+                self.port_id_attr_on_a.to_attribute(),
+                self.chan_id_attr_on_a.to_attribute(),
+                self.port_id_attr_on_b.to_attribute(),
+                self.chan_id_attr_on_b.to_attribute(),
+                self.channel_ordering_attr.clone().into(),
+            ],
+        }
+    }
+
+    /// Moves the timeout height from revision `from` to revision `to`, e.g.
+    /// to replay events of a testnet against a mainnet revision.
+    pub fn remap_timeout_revision(&mut self, from: u64, to: u64) {
+        self.timeout_height_attr_on_b.remap_revision(from, to);
+    }
+
+    /// The canonical ABCI event kind of this event.
+    pub const fn kind() -> &'static str {
         TIMEOUT_EVENT
     }
+
+    pub fn event_type(&self) -> &str {
+        Self::kind()
+    }
 }
 
 impl TryFrom<TimeoutPacket> for abci::Event {
     type Error = ChannelError;
 
     fn try_from(v: TimeoutPacket) -> Result<Self, Self::Error> {
-        Ok(abci::Event {
-            kind: TIMEOUT_EVENT.to_string(),
-            attributes: vec![
-                v.timeout_height_attr_on_b.into(),
-                v.timeout_timestamp_attr_on_b.into(),
-                v.seq_attr_on_a.into(),
-                v.port_id_attr_on_a.into(),
-                v.chan_id_attr_on_a.into(),
-                v.port_id_attr_on_b.into(),
-                v.chan_id_attr_on_b.into(),
-                v.channel_ordering_attr.into(),
-            ],
+        v.to_abci_event(EmissionProfile::Default)
+    }
+}
+
+impl TryFrom<abci::Event> for TimeoutPacket {
+    type Error = ChannelError;
+
+    fn try_from(event: abci::Event) -> Result<Self, Self::Error> {
+        let attrs = PacketEventAttributes::parse(&event, TIMEOUT_EVENT)?;
+
+        let timeout_height_attr_on_b =
+            required_attribute(attrs.timeout_height, PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY)?;
+        let timeout_timestamp_attr_on_b =
+            required_attribute(attrs.timeout_timestamp, PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY)?;
+        validate_timeout_present(
+            &timeout_height_attr_on_b.timeout_height,
+            &timeout_timestamp_attr_on_b.timeout_timestamp,
+        )?;
+
+        Ok(Self {
+            timeout_height_attr_on_b,
+            timeout_timestamp_attr_on_b,
+            seq_attr_on_a: required_attribute(attrs.sequence, PKT_SEQ_ATTRIBUTE_KEY)?,
+            port_id_attr_on_a: required_attribute(attrs.src_port_id, PKT_SRC_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_a: required_attribute(
+                attrs.src_channel_id,
+                PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            port_id_attr_on_b: required_attribute(attrs.dst_port_id, PKT_DST_PORT_ATTRIBUTE_KEY)?,
+            chan_id_attr_on_b: required_attribute(
+                attrs.dst_channel_id,
+                PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+            )?,
+            channel_ordering_attr: required_attribute(
+                attrs.channel_ordering,
+                PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+            )?,
         })
     }
 }
 
+/// Collects the attributes of an event into a JSON object keyed by their
+/// canonical attribute keys, with string values. Attributes that are not
+/// valid UTF-8 are skipped.
+#[cfg(feature = "serde")]
+fn attribute_map(event: &abci::Event) -> serde_json::Map<String, serde_json::Value> {
+    event
+        .attributes
+        .iter()
+        .filter_map(|attr| {
+            Some((
+                attr.key_str().ok()?.to_string(),
+                serde_json::Value::String(attr.value_str().ok()?.to_string()),
+            ))
+        })
+        .collect()
+}
+
+/// Implements `attribute_map`, which returns a flat JSON object ready to be
+/// merged into a structured log line.
+#[cfg(feature = "serde")]
+macro_rules! impl_attribute_map {
+    (infallible: $($event:ty),+ $(,)?) => {
+        $(
+            impl $event {
+                /// The attributes of the event, keyed by their canonical keys.
+                pub fn attribute_map(&self) -> serde_json::Map<String, serde_json::Value> {
+                    attribute_map(&abci::Event::from(self.clone()))
+                }
+            }
+        )+
+    };
+    (fallible: $($event:ty),+ $(,)?) => {
+        $(
+            impl $event {
+                /// The attributes of the event, keyed by their canonical keys.
+                pub fn attribute_map(
+                    &self,
+                ) -> Result<serde_json::Map<String, serde_json::Value>, ChannelError> {
+                    abci::Event::try_from(self.clone()).map(|event| attribute_map(&event))
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "serde")]
+impl_attribute_map!(infallible:
+    OpenInit,
+    OpenTry,
+    OpenAck,
+    OpenConfirm,
+    CloseInit,
+    CloseConfirm,
+    ChannelHandshakeEvent,
+    ChannelClosed,
+);
+#[cfg(feature = "serde")]
+impl_attribute_map!(fallible:
+    SendPacket,
+    ReceivePacket,
+    WriteAcknowledgement,
+    AcknowledgePacket,
+    TimeoutPacket,
+);
+
 #[cfg(test)]
 mod tests {
+    use ibc_core_client_types::Height;
     use tendermint::abci::Event as AbciEvent;
 
+    use super::packet_attributes::{
+        HexCase, PacketAttribute, PacketAttributes, PacketTimeout, PKT_DATA_ATTRIBUTE_KEY,
+    };
     use super::*;
+    use crate::error::HexDecodeError;
 
     #[test]
     fn ibc_to_abci_channel_events() {
@@ -1264,4 +2982,2278 @@ mod tests {
             }
         }
     }
+
+    #[cfg(feature = "lenient-attributes")]
+    #[test]
+    fn mixed_case_attribute_keys() {
+        let port_id_attr: abci::EventAttribute = ("Port_Id", "transfer").into();
+        assert_eq!(
+            PortIdAttribute::try_from(port_id_attr).unwrap().port_id,
+            PortId::transfer()
+        );
+
+        let channel_id_attr: abci::EventAttribute = ("CHANNEL_ID", "channel-0").into();
+        assert_eq!(
+            ChannelIdAttribute::try_from(channel_id_attr)
+                .unwrap()
+                .channel_id,
+            ChannelId::zero()
+        );
+
+        let version_attr: abci::EventAttribute = ("Version", "ics20-1").into();
+        assert!(VersionAttribute::try_from(version_attr).is_ok());
+    }
+
+    #[cfg(feature = "lenient-attributes")]
+    #[test]
+    fn send_packet_with_mixed_case_keys() {
+        let mut event = send_packet_event(1);
+        for attribute in &mut event.attributes {
+            let key = attribute
+                .key_str()
+                .unwrap()
+                .split('_')
+                .map(|word| word[..1].to_ascii_uppercase() + &word[1..])
+                .collect::<Vec<_>>()
+                .join("_");
+            *attribute = (key, attribute.value_str().unwrap().to_string()).into();
+        }
+        assert!(event
+            .attributes
+            .iter()
+            .any(|attr| attr.key_bytes() == b"Packet_Sequence"));
+
+        let send_packet = SendPacket::try_from(event).unwrap();
+        assert_eq!(
+            send_packet,
+            SendPacket::try_from(send_packet_event(1)).unwrap()
+        );
+        assert!(send_packet.extra_attributes().is_empty());
+    }
+
+    #[cfg(not(feature = "lenient-attributes"))]
+    #[test]
+    fn mixed_case_attribute_keys_are_rejected() {
+        let port_id_attr: abci::EventAttribute = ("Port_Id", "transfer").into();
+        assert!(PortIdAttribute::try_from(port_id_attr).is_err());
+    }
+
+    #[cfg(feature = "null-terminated-values")]
+    #[test]
+    fn null_terminated_attribute_values() {
+        let channel_id_attr: abci::EventAttribute = ("channel_id", "channel-0\0").into();
+        assert_eq!(
+            ChannelIdAttribute::try_from(channel_id_attr)
+                .unwrap()
+                .channel_id,
+            ChannelId::zero()
+        );
+
+        // Only a single null byte is stripped.
+        let channel_id_attr: abci::EventAttribute = ("channel_id", "channel-0\0\0").into();
+        assert!(ChannelIdAttribute::try_from(channel_id_attr).is_err());
+    }
+
+    #[cfg(feature = "null-terminated-values")]
+    #[test]
+    fn null_terminated_packet_data_is_kept() {
+        let mut packet = mock_packet(1);
+        packet.data = b"payload\0".to_vec();
+
+        let event = SendPacket::new(packet.clone(), Order::Unordered, ConnectionId::zero());
+        let abci_event: AbciEvent = event.clone().try_into().unwrap();
+        let parsed = SendPacket::try_from(abci_event).unwrap();
+
+        assert_eq!(parsed, event);
+        assert_eq!(parsed.packet_data(), b"payload\0");
+    }
+
+    #[cfg(not(feature = "null-terminated-values"))]
+    #[test]
+    fn null_terminated_attribute_values_are_rejected() {
+        let channel_id_attr: abci::EventAttribute = ("channel_id", "channel-0\0").into();
+        assert!(ChannelIdAttribute::try_from(channel_id_attr).is_err());
+    }
+
+    fn send_packet_event(seq: u64) -> AbciEvent {
+        SendPacket::new(mock_packet(seq), Order::Unordered, ConnectionId::zero())
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn send_packet_abci_round_trip() {
+        let send_packet = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero());
+        let abci_event: AbciEvent = send_packet.clone().try_into().unwrap();
+
+        assert_eq!(SendPacket::try_from(abci_event).unwrap(), send_packet);
+    }
+
+    #[test]
+    fn acknowledge_packet_abci_round_trip() {
+        let ack_packet =
+            AcknowledgePacket::new(mock_packet(3), Order::Ordered, ConnectionId::new(2));
+        let abci_event: AbciEvent = ack_packet.clone().try_into().unwrap();
+
+        assert_eq!(AcknowledgePacket::try_from(abci_event).unwrap(), ack_packet);
+    }
+
+    #[test]
+    fn parse_packet_event_with_repeated_attribute() {
+        for (key, value) in [
+            (PKT_SEQ_ATTRIBUTE_KEY, "2"),
+            (PKT_SRC_CHANNEL_ATTRIBUTE_KEY, "channel-5"),
+        ] {
+            let mut event = send_packet_event(1);
+            event.attributes.push((key, value).into());
+
+            assert!(matches!(
+                SendPacket::try_from(event),
+                Err(ChannelError::DuplicateAttributeKey { attribute_key }) if attribute_key == key
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_packet_event_with_non_utf8_key() {
+        let mut event = send_packet_event(1);
+        event
+            .attributes
+            .push(abci::EventAttribute::V034(abci::v0_34::EventAttribute {
+                key: vec![0xff],
+                value: b"value".to_vec(),
+                index: false,
+            }));
+
+        assert!(matches!(
+            SendPacket::try_from(event),
+            Err(ChannelError::InvalidAttributeKey { .. })
+        ));
+    }
+
+    #[test]
+    fn iterate_send_packets_of_mixed_block() {
+        let recv_packet: AbciEvent =
+            ReceivePacket::new(mock_packet(7), Order::Unordered, ConnectionId::zero())
+                .try_into()
+                .unwrap();
+        let mut malformed = send_packet_event(4);
+        malformed.attributes.retain(|attr| {
+            attr.key_str()
+                .map_or(true, |key| key != PKT_SEQ_ATTRIBUTE_KEY)
+        });
+
+        let block = vec![
+            OpenInit::new(
+                PortId::transfer(),
+                ChannelId::zero(),
+                PortId::transfer(),
+                ConnectionId::zero(),
+                Version::new("ics20-1".to_string()),
+            )
+            .into(),
+            send_packet_event(1),
+            recv_packet.clone(),
+            send_packet_event(2),
+            malformed,
+            recv_packet,
+            send_packet_event(3),
+        ];
+
+        let results: Vec<_> = iter_send_packets(&block).collect();
+        assert_eq!(results.len(), 4);
+        assert!(results[2].is_err());
+
+        let send_packets: Vec<_> = results.into_iter().filter_map(Result::ok).collect();
+        assert_eq!(send_packets.len(), 3);
+        assert_eq!(send_packets.first().unwrap().seq_on_a(), &Sequence::from(1));
+        assert_eq!(send_packets.last().unwrap().seq_on_a(), &Sequence::from(3));
+    }
+
+    #[test]
+    fn send_packet_preserves_custom_attributes() {
+        let mut abci_event = send_packet_event(1);
+        abci_event
+            .attributes
+            .push(("fee_payer", "cosmos1payer").into());
+        abci_event.attributes.push(("memo", "relayed-by-us").into());
+
+        let send_packet = SendPacket::try_from(abci_event.clone()).unwrap();
+        assert_eq!(send_packet.seq_on_a(), &Sequence::from(1));
+
+        let extra_keys: Vec<_> = send_packet
+            .extra_attributes()
+            .iter()
+            .map(|attr| attr.key_str().unwrap())
+            .collect();
+        assert_eq!(extra_keys, vec!["fee_payer", "memo"]);
+
+        let round_tripped: AbciEvent = send_packet.try_into().unwrap();
+        assert_eq!(round_tripped, abci_event);
+    }
+
+    #[test]
+    fn timeout_height_attribute_conveniences() {
+        let at: abci::EventAttribute =
+            TimeoutHeightAttribute::from(Height::new(1, 10).unwrap()).into();
+        assert_eq!(at.key_str().unwrap(), PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY);
+        assert_eq!(at.value_str().unwrap(), "1-10");
+
+        let never: abci::EventAttribute = TimeoutHeightAttribute::never().into();
+        assert_eq!(never.value_str().unwrap(), "0-0");
+    }
+
+    #[test]
+    fn validate_handshake_event_port() {
+        let registered: BTreeSet<PortId> = [PortId::transfer()].into_iter().collect();
+        let open_try = |port_id: PortId| -> ChannelHandshakeEvent {
+            OpenTry::new(
+                port_id,
+                ChannelId::new(1),
+                PortId::transfer(),
+                ChannelId::zero(),
+                ConnectionId::zero(),
+                Version::new("ics20-1".to_string()),
+            )
+            .into()
+        };
+
+        assert!(validate_handshake_port(&open_try(PortId::transfer()), &registered).is_ok());
+
+        let unknown_port: PortId = "icahost".parse().unwrap();
+        assert!(matches!(
+            validate_handshake_port(&open_try(unknown_port.clone()), &registered),
+            Err(ChannelError::UnknownPort { port_id }) if port_id == unknown_port
+        ));
+    }
+
+    #[test]
+    fn send_packet_eq_ignoring_deprecated() {
+        let dual_attr_event = send_packet_event(1);
+        let mut hex_only_event = dual_attr_event.clone();
+        hex_only_event.attributes.retain(|attr| {
+            attr.key_str()
+                .map_or(true, |key| key != PKT_DATA_ATTRIBUTE_KEY)
+        });
+        // Newer chains may also attach attributes we don't know about.
+        hex_only_event
+            .attributes
+            .push(("packet_data_encoding", "hex").into());
+        assert_ne!(dual_attr_event.attributes, hex_only_event.attributes);
+
+        let dual_attr = SendPacket::try_from(dual_attr_event).unwrap();
+        let hex_only = SendPacket::try_from(hex_only_event).unwrap();
+
+        assert_ne!(dual_attr, hex_only);
+        assert!(dual_attr.eq_ignoring_deprecated(&hex_only));
+
+        let other = SendPacket::try_from(send_packet_event(2)).unwrap();
+        assert!(!dual_attr.eq_ignoring_deprecated(&other));
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn empty_acknowledgement_attribute_is_rejected() {
+        // Codecs bypass the non-empty check of `Acknowledgement::try_from`.
+        let empty_ack: Acknowledgement = borsh::from_slice(&[0, 0, 0, 0]).unwrap();
+        let attr = AcknowledgementAttribute::from(empty_ack);
+
+        assert!(matches!(
+            Vec::<abci::EventAttribute>::try_from(attr),
+            Err(ChannelError::EmptyAcknowledgement)
+        ));
+    }
+
+    #[test]
+    fn strict_packet_channel_id_attribute() {
+        let attr = |value: &str| -> abci::EventAttribute { ("packet_src_channel", value).into() };
+
+        assert_eq!(
+            SrcChannelIdAttribute::try_from_strict(attr("channel-5"))
+                .unwrap()
+                .src_channel_id,
+            ChannelId::new(5)
+        );
+
+        // Leading zeros are already rejected by the identifier validation
+        assert!(SrcChannelIdAttribute::try_from(attr("channel-05")).is_err());
+        assert!(SrcChannelIdAttribute::try_from_strict(attr("channel-05")).is_err());
+        assert!(SrcChannelIdAttribute::try_from_strict(attr("transfer/channel-5")).is_err());
+
+        let dst: abci::EventAttribute = ("packet_dst_channel", "channel-05").into();
+        assert!(DstChannelIdAttribute::try_from_strict(dst).is_err());
+    }
+
+    #[test]
+    fn hex_attribute_decode_errors() {
+        let odd_length: abci::EventAttribute = ("packet_data_hex", "abc").into();
+        assert!(matches!(
+            PacketDataAttribute::try_from(odd_length),
+            Err(ChannelError::InvalidHexAttributeValue {
+                error: HexDecodeError::OddLength { length: 3 },
+                ..
+            })
+        ));
+
+        let non_hex: abci::EventAttribute = ("packet_ack_hex", "0a1g").into();
+        assert!(matches!(
+            AcknowledgementAttribute::try_from(non_hex),
+            Err(ChannelError::InvalidHexAttributeValue {
+                error: HexDecodeError::InvalidCharacter {
+                    character: 'g',
+                    position: 3,
+                },
+                ..
+            })
+        ));
+
+        let upper_case: abci::EventAttribute = ("packet_data_hex", "0A0b").into();
+        assert_eq!(
+            PacketDataAttribute::try_from(upper_case)
+                .unwrap()
+                .packet_data,
+            vec![0x0a, 0x0b]
+        );
+    }
+
+    #[test]
+    fn ordering_consistency_of_send_and_receive() {
+        let send = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero());
+
+        let recv = ReceivePacket::new(mock_packet(1), Order::Unordered, ConnectionId::new(1));
+        assert!(validate_ordering_consistency(&send, &recv).is_ok());
+
+        let recv = ReceivePacket::new(mock_packet(1), Order::Ordered, ConnectionId::new(1));
+        assert!(matches!(
+            validate_ordering_consistency(&send, &recv),
+            Err(ChannelError::OrderingMismatch {
+                send_ordering: Order::Unordered,
+                recv_ordering: Order::Ordered,
+            })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_attributes_of_either_casing() {
+        for json in [
+            r#"{"src_channel_id":"channel-7"}"#,
+            r#"{"srcChannelId":"channel-7"}"#,
+        ] {
+            let attr: SrcChannelIdAttribute = serde_json::from_str(json).unwrap();
+            assert_eq!(attr.src_channel_id, ChannelId::new(7));
+        }
+
+        for json in [
+            r#"{"timeout_height":{"revision_number":1,"revision_height":5}}"#,
+            r#"{"timeoutHeight":{"revision_number":1,"revision_height":5}}"#,
+        ] {
+            let attr: TimeoutHeightAttribute = serde_json::from_str(json).unwrap();
+            assert_eq!(
+                attr.timeout_height,
+                TimeoutHeight::At(Height::new(1, 5).unwrap())
+            );
+        }
+
+        let attr = SrcChannelIdAttribute::from(ChannelId::new(7));
+        assert_eq!(
+            serde_json::to_string(&attr).unwrap(),
+            r#"{"src_channel_id":"channel-7"}"#
+        );
+    }
+
+    #[test]
+    fn event_kinds_match_ibc_go() {
+        let expected = [
+            (OpenInit::kind(), "channel_open_init"),
+            (OpenTry::kind(), "channel_open_try"),
+            (OpenAck::kind(), "channel_open_ack"),
+            (OpenConfirm::kind(), "channel_open_confirm"),
+            (CloseInit::kind(), "channel_close_init"),
+            (CloseConfirm::kind(), "channel_close_confirm"),
+            (ChannelClosed::kind(), "channel_close"),
+            (SendPacket::kind(), "send_packet"),
+            (ReceivePacket::kind(), "recv_packet"),
+            (WriteAcknowledgement::kind(), "write_acknowledgement"),
+            (AcknowledgePacket::kind(), "acknowledge_packet"),
+            (TimeoutPacket::kind(), "timeout_packet"),
+        ];
+
+        for (kind, ibc_go_kind) in expected {
+            assert_eq!(kind, ibc_go_kind);
+        }
+        assert_eq!(SEND_PACKET_EVENT, "send_packet");
+        assert_eq!(RECV_PACKET_EVENT, "recv_packet");
+    }
+
+    #[test]
+    fn packet_event_ordering_predicates() {
+        let send = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero());
+        assert!(send.is_unordered() && !send.is_ordered());
+
+        let recv = ReceivePacket::new(mock_packet(1), Order::Ordered, ConnectionId::zero());
+        assert!(recv.is_ordered() && !recv.is_unordered());
+
+        let ack = AcknowledgePacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero());
+        assert!(ack.is_unordered() && !ack.is_ordered());
+
+        let timeout = TimeoutPacket::new(mock_packet(1), Order::None);
+        assert!(!timeout.is_ordered() && !timeout.is_unordered());
+    }
+
+    #[cfg(feature = "legacy-attributes")]
+    #[test]
+    fn split_legacy_packet_timeout() {
+        use super::packet_attributes::LegacyPacketTimeoutAttribute;
+
+        let attr: abci::EventAttribute = ("packet_timeout", "1-100,1000").into();
+        let (timeout_height, timeout_timestamp) = LegacyPacketTimeoutAttribute::try_from(attr)
+            .unwrap()
+            .split();
+
+        assert_eq!(
+            timeout_height.timeout_height,
+            TimeoutHeight::At(Height::new(1, 100).unwrap())
+        );
+        assert_eq!(
+            timeout_timestamp.timeout_timestamp,
+            Timestamp::from_nanoseconds(1000).unwrap()
+        );
+
+        let attr: abci::EventAttribute = ("packet_timeout", "0-0,5").into();
+        let legacy = LegacyPacketTimeoutAttribute::try_from(attr).unwrap();
+        assert_eq!(legacy.timeout_height, TimeoutHeight::Never);
+
+        let attr: abci::EventAttribute = ("packet_timeout", "1-100").into();
+        assert!(LegacyPacketTimeoutAttribute::try_from(attr).is_err());
+    }
+
+    #[cfg(feature = "legacy-attributes")]
+    #[test]
+    fn parse_send_packet_with_legacy_packet_timeout() {
+        let mut packet = mock_packet(1);
+        packet.timeout_height_on_b = TimeoutHeight::At(Height::new(1, 100).unwrap());
+        let send_packet = SendPacket::new(packet, Order::Unordered, ConnectionId::zero());
+
+        let mut legacy: AbciEvent = send_packet.clone().try_into().unwrap();
+        legacy.attributes.retain(|attr| {
+            let key = attr.key_str().unwrap();
+            key != PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY && key != PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY
+        });
+        legacy
+            .attributes
+            .push(("packet_timeout", "1-100,1000").into());
+
+        assert_eq!(SendPacket::try_from(legacy.clone()).unwrap(), send_packet);
+
+        // The separate attributes take precedence.
+        let mut both = legacy;
+        both.attributes
+            .push((PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY, "2000").into());
+        let parsed = SendPacket::try_from(both).unwrap();
+        assert_eq!(
+            parsed.timeout_height_on_b(),
+            send_packet.timeout_height_on_b()
+        );
+        assert_eq!(
+            parsed.timeout_timestamp_on_b(),
+            &Timestamp::from_nanoseconds(2000).unwrap()
+        );
+    }
+
+    #[test]
+    fn handshake_events_from_attributes() {
+        let version = Version::new("ics20-1".to_string());
+
+        let open_init = OpenInit::new(
+            PortId::transfer(),
+            ChannelId::zero(),
+            PortId::transfer(),
+            ConnectionId::zero(),
+            version.clone(),
+        );
+        let attributes = AbciEvent::from(open_init.clone()).attributes;
+        assert_eq!(OpenInit::try_from(attributes).unwrap(), open_init);
+
+        let open_try = OpenTry::new(
+            PortId::transfer(),
+            ChannelId::new(1),
+            PortId::transfer(),
+            ChannelId::zero(),
+            ConnectionId::new(1),
+            version,
+        );
+        let attributes = AbciEvent::from(open_try.clone()).attributes;
+        assert_eq!(OpenTry::try_from(attributes).unwrap(), open_try);
+
+        let open_ack = OpenAck::new(
+            PortId::transfer(),
+            ChannelId::zero(),
+            PortId::transfer(),
+            ChannelId::new(1),
+            ConnectionId::zero(),
+        );
+        let attributes = AbciEvent::from(open_ack.clone()).attributes;
+        assert_eq!(OpenAck::try_from(attributes).unwrap(), open_ack);
+
+        let open_confirm = OpenConfirm::new(
+            PortId::transfer(),
+            ChannelId::new(1),
+            PortId::transfer(),
+            ChannelId::zero(),
+            ConnectionId::new(1),
+        );
+        let mut attributes = AbciEvent::from(open_confirm.clone()).attributes;
+        assert_eq!(
+            OpenConfirm::try_from(attributes.clone()).unwrap(),
+            open_confirm
+        );
+
+        attributes.retain(|attr| attr.key_str().unwrap() != "counterparty_channel_id");
+        assert!(matches!(
+            OpenConfirm::try_from(attributes),
+            Err(ChannelError::MissingAttributeKey { attribute_key })
+                if attribute_key == "counterparty_channel_id"
+        ));
+    }
+
+    #[test]
+    fn packet_data_utf8_detection() {
+        assert!(PacketDataAttribute::from(b"{\"amount\":\"100\"}".to_vec()).is_utf8());
+        assert!(PacketDataAttribute::from(Vec::new()).is_utf8());
+        assert!(!PacketDataAttribute::from(vec![0xff, 0xfe]).is_utf8());
+    }
+
+    #[test]
+    fn redacted_send_packet_omits_payload() {
+        let send_packet = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero());
+        let original = send_packet.clone();
+
+        let redacted = send_packet.redacted();
+        assert_eq!(send_packet, original);
+
+        let value_of = |key: &str| {
+            redacted
+                .attributes
+                .iter()
+                .find(|attr| attr.key_str().unwrap() == key)
+                .map(|attr| attr.value_str().unwrap().to_string())
+        };
+        assert_eq!(value_of("packet_data_len").as_deref(), Some("16"));
+        assert_eq!(
+            value_of("packet_data_sha256").map(|hash| hash.len()),
+            Some(64)
+        );
+        assert_eq!(value_of("packet_sequence").as_deref(), Some("1"));
+        assert!(value_of("packet_data").is_none());
+        assert!(value_of("packet_data_hex").is_none());
+
+        let payload = "{\"amount\":\"100\"}";
+        let payload_hex = "7b22616d6f756e74223a22313030227d";
+        assert!(redacted.attributes.iter().all(|attr| {
+            let value = attr.value_str().unwrap();
+            !value.contains(payload) && !value.contains(payload_hex)
+        }));
+    }
+
+    #[test]
+    fn ordering_against_connection_features() {
+        let chan_id = ChannelId::new(2);
+        let features = vec!["ORDER_UNORDERED".to_string()];
+
+        assert!(validate_ordering_supported(&chan_id, Order::Unordered, &features).is_ok());
+        assert!(matches!(
+            validate_ordering_supported(&chan_id, Order::Ordered, &features),
+            Err(ChannelError::OrderingNotSupported {
+                channel_id,
+                ordering: Order::Ordered,
+            }) if channel_id == chan_id
+        ));
+    }
+
+    #[test]
+    fn parse_attributes_from_raw_pairs() {
+        let sequence = SequenceAttribute::try_from((&b"packet_sequence"[..], &b"42"[..])).unwrap();
+        assert_eq!(sequence.sequence, Sequence::from(42));
+
+        let key = b"packet_data_hex".to_vec();
+        let value = b"0a0b".to_vec();
+        let packet_data = PacketDataAttribute::try_from((&key[..], &value[..])).unwrap();
+        assert_eq!(packet_data.packet_data, vec![0x0a, 0x0b]);
+
+        let port_id = PortIdAttribute::try_from((&b"port_id"[..], &b"transfer"[..])).unwrap();
+        assert_eq!(port_id.port_id, PortId::transfer());
+
+        assert!(matches!(
+            SequenceAttribute::try_from((&b"packet_sequence"[..], &[0xff][..])),
+            Err(ChannelError::InvalidAttributeValue { .. })
+        ));
+        assert!(matches!(
+            SequenceAttribute::try_from((&b"packet_src_port"[..], &b"42"[..])),
+            Err(ChannelError::InvalidAttributeKey { .. })
+        ));
+    }
+
+    #[test]
+    fn channel_ordering_string_and_proto_forms() {
+        for (value, expected) in [
+            ("1", Order::Unordered),
+            ("2", Order::Ordered),
+            ("ORDER_UNORDERED", Order::Unordered),
+            ("ORDER_ORDERED", Order::Ordered),
+        ] {
+            let attr: abci::EventAttribute = ("packet_channel_ordering", value).into();
+            assert_eq!(
+                ChannelOrderingAttribute::try_from(attr).unwrap().order,
+                expected
+            );
+        }
+
+        let attr: abci::EventAttribute = ("packet_channel_ordering", "3").into();
+        assert!(ChannelOrderingAttribute::try_from(attr).is_err());
+
+        let proto_attr = ChannelOrderingAttribute::from(Order::Ordered).into_proto_attribute();
+        assert_eq!(proto_attr.value_str().unwrap(), "2");
+        assert_eq!(
+            ChannelOrderingAttribute::try_from(proto_attr)
+                .unwrap()
+                .order,
+            Order::Ordered
+        );
+    }
+
+    #[test]
+    fn hex_attribute_casing_consistency() {
+        let lowercase: Vec<abci::EventAttribute> = vec![
+            ("packet_data_hex", "0a0b").into(),
+            ("packet_sequence", "1").into(),
+            ("packet_ack_hex", "0c").into(),
+        ];
+        assert!(validate_encoding_consistency(&lowercase).is_ok());
+
+        let digits_only: Vec<abci::EventAttribute> = vec![
+            ("packet_data_hex", "0A0B").into(),
+            ("packet_ack_hex", "01").into(),
+        ];
+        assert!(validate_encoding_consistency(&digits_only).is_ok());
+
+        let mixed: Vec<abci::EventAttribute> = vec![
+            ("packet_data_hex", "0A0B").into(),
+            ("packet_ack_hex", "0c").into(),
+        ];
+        assert!(matches!(
+            validate_encoding_consistency(&mixed),
+            Err(ChannelError::InconsistentHexCasing { attribute_key })
+                if attribute_key == "packet_ack_hex"
+        ));
+    }
+
+    #[cfg(feature = "parity-scale-codec")]
+    #[test]
+    fn validate_catches_malformed_scale_decoded_attributes() {
+        use parity_scale_codec::{Decode, Encode};
+
+        let valid = PortIdAttribute::from(PortId::transfer());
+        assert!(valid.validate().is_ok());
+
+        // `PortId` is SCALE-encoded as its inner string.
+        let malformed_bytes = "invalid port/".to_string().encode();
+        let malformed = PortIdAttribute::decode(&mut malformed_bytes.as_slice()).unwrap();
+        assert!(matches!(
+            malformed.validate(),
+            Err(ChannelError::InvalidIdentifier(_))
+        ));
+
+        let malformed = SrcChannelIdAttribute::decode(&mut malformed_bytes.as_slice()).unwrap();
+        assert!(malformed.validate().is_err());
+
+        let malformed_bytes = Vec::<u8>::new().encode();
+        let malformed = AcknowledgementAttribute::decode(&mut malformed_bytes.as_slice()).unwrap();
+        assert!(matches!(
+            malformed.validate(),
+            Err(ChannelError::EmptyAcknowledgement)
+        ));
+    }
+
+    #[test]
+    fn lazy_packet_data_decoding() {
+        let lazy = LazyPacketEvent::new(send_packet_event(1));
+
+        assert_eq!(lazy.kind(), "send_packet");
+        assert_eq!(lazy.attribute("packet_src_channel"), Some("channel-0"));
+        assert_eq!(lazy.packet_data().unwrap(), mock_packet(1).data.as_slice());
+        // The second access is served from the cache.
+        assert_eq!(lazy.packet_data().unwrap(), mock_packet(1).data.as_slice());
+
+        let mut malformed = send_packet_event(2);
+        malformed
+            .attributes
+            .retain(|attr| !attr.key_str().unwrap().starts_with("packet_data"));
+        malformed.attributes.push(("packet_data_hex", "zz").into());
+        let lazy = LazyPacketEvent::new(malformed);
+        assert_eq!(lazy.attribute("packet_sequence"), Some("2"));
+        assert!(lazy.packet_data().is_err());
+    }
+
+    #[test]
+    fn channel_endpoint_from_open_try_attributes() {
+        let open_try = OpenTry::new(
+            PortId::transfer(),
+            ChannelId::new(1),
+            PortId::transfer(),
+            ChannelId::zero(),
+            ConnectionId::new(2),
+            Version::new("ics20-1".to_string()),
+        );
+        let mut attributes = AbciEvent::from(open_try).attributes;
+
+        assert_eq!(
+            ChannelEndpoint::try_from(attributes.clone()).unwrap(),
+            ChannelEndpoint {
+                connection_id: ConnectionId::new(2),
+                port_id: PortId::transfer(),
+                channel_id: ChannelId::new(1),
+            }
+        );
+
+        attributes.retain(|attr| attr.key_str().unwrap() != "connection_id");
+        assert!(matches!(
+            ChannelEndpoint::try_from(attributes),
+            Err(ChannelError::MissingAttributeKey { attribute_key })
+                if attribute_key == "connection_id"
+        ));
+    }
+
+    #[test]
+    fn event_attribution_audit() {
+        let events = vec![
+            (send_packet_event(1), Some(0)),
+            (send_packet_event(2), Some(1)),
+        ];
+        assert!(validate_event_attribution(&events, 2).is_ok());
+
+        assert!(matches!(
+            validate_event_attribution(&events, 1),
+            Err(ChannelError::EventMsgIndexOutOfRange {
+                msg_index: 1,
+                num_msgs: 1,
+                ..
+            })
+        ));
+
+        let orphaned = vec![
+            (send_packet_event(1), Some(0)),
+            (send_packet_event(2), None),
+        ];
+        assert!(matches!(
+            validate_event_attribution(&orphaned, 2),
+            Err(ChannelError::OrphanedEvent { event_kind, position: 1 })
+                if event_kind == "send_packet"
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn invalid_attribute_value_exposes_parse_error_as_source() {
+        use std::error::Error;
+
+        use ibc_core_client_types::HeightError;
+        use ibc_core_host_types::error::IdentifierError;
+
+        let attr: abci::EventAttribute = ("packet_timeout_height", "1-x").into();
+        let err = TimeoutHeightAttribute::try_from(attr).unwrap_err();
+        assert!(err
+            .source()
+            .and_then(|source| source.downcast_ref::<HeightError>())
+            .is_some());
+
+        let attr: abci::EventAttribute = ("packet_sequence", "-1").into();
+        let err = SequenceAttribute::try_from(attr).unwrap_err();
+        assert!(err
+            .source()
+            .and_then(|source| source.downcast_ref::<IdentifierError>())
+            .is_some());
+
+        let attr: abci::EventAttribute = ("packet_timeout_timestamp", "soon").into();
+        let err = TimeoutTimestampAttribute::try_from(attr).unwrap_err();
+        assert!(err
+            .source()
+            .and_then(|source| source.downcast_ref::<core::num::ParseIntError>())
+            .is_some());
+    }
+
+    #[test]
+    fn send_packet_canonical_attributes_match_ibc_go() {
+        // Captured from an ibc-go `send_packet` event of an ICS-20 transfer.
+        let ibc_go_attributes = [
+            ("packet_data", "{\"amount\":\"100\"}"),
+            ("packet_data_hex", "7b22616d6f756e74223a22313030227d"),
+            ("packet_timeout_height", "0-0"),
+            ("packet_timeout_timestamp", "1000"),
+            ("packet_sequence", "1"),
+            ("packet_src_port", "transfer"),
+            ("packet_src_channel", "channel-0"),
+            ("packet_dst_port", "transfer"),
+            ("packet_dst_channel", "channel-1"),
+            ("packet_channel_ordering", "ORDER_UNORDERED"),
+            ("packet_connection", "connection-0"),
+        ];
+
+        let send_packet = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero())
+            .with_extra_attributes(vec![("custom", "value").into()]);
+        let attributes = send_packet.canonical_attributes().unwrap();
+
+        let emitted: Vec<(&str, &str)> = attributes
+            .iter()
+            .map(|attr| (attr.key_str().unwrap(), attr.value_str().unwrap()))
+            .collect();
+        assert_eq!(emitted, ibc_go_attributes);
+    }
+
+    #[test]
+    fn sequence_range_of_send_packet() {
+        let send_packet = SendPacket::new(mock_packet(5), Order::Unordered, ConnectionId::zero());
+
+        assert!(validate_sequence_range(&send_packet, Sequence::from(5)).is_ok());
+        assert!(validate_sequence_range(&send_packet, Sequence::from(10)).is_ok());
+        assert!(matches!(
+            validate_sequence_range(&send_packet, Sequence::from(4)),
+            Err(ChannelError::SequenceOutOfRange { sequence, max_expected })
+                if sequence == Sequence::from(5) && max_expected == Sequence::from(4)
+        ));
+    }
+
+    #[test]
+    fn packet_from_captured_send_packet_event() {
+        let captured_event = |packet_data_hex: &str| AbciEvent {
+            kind: "send_packet".to_string(),
+            attributes: vec![
+                ("packet_data_hex", packet_data_hex).into(),
+                ("packet_timeout_height", "1-1200").into(),
+                ("packet_timeout_timestamp", "1700000000000000000").into(),
+                ("packet_sequence", "42").into(),
+                ("packet_src_port", "transfer").into(),
+                ("packet_src_channel", "channel-0").into(),
+                ("packet_dst_port", "transfer").into(),
+                ("packet_dst_channel", "channel-7").into(),
+                ("packet_channel_ordering", "ORDER_UNORDERED").into(),
+                ("packet_connection", "connection-0").into(),
+            ],
+        };
+
+        let send_packet =
+            SendPacket::try_from(captured_event("7b22616d6f756e74223a22313030227d")).unwrap();
+        let packet = Packet::try_from(send_packet).unwrap();
+
+        assert_eq!(packet.seq_on_a, Sequence::from(42));
+        assert_eq!(packet.port_id_on_a, PortId::transfer());
+        assert_eq!(packet.chan_id_on_a, ChannelId::zero());
+        assert_eq!(packet.port_id_on_b, PortId::transfer());
+        assert_eq!(packet.chan_id_on_b, ChannelId::new(7));
+        assert_eq!(packet.data, b"{\"amount\":\"100\"}".to_vec());
+        assert_eq!(
+            packet.timeout_height_on_b,
+            TimeoutHeight::At(Height::new(1, 1200).unwrap())
+        );
+        assert_eq!(
+            packet.timeout_timestamp_on_b,
+            Timestamp::from_nanoseconds(1_700_000_000_000_000_000).unwrap()
+        );
+
+        let send_packet = SendPacket::try_from(captured_event("")).unwrap();
+        assert!(matches!(
+            Packet::try_from(send_packet),
+            Err(PacketError::ZeroPacketData)
+        ));
+    }
+
+    #[test]
+    fn parse_write_ack_under_legacy_kind() {
+        let write_ack = WriteAcknowledgement::new(
+            mock_packet(1),
+            Acknowledgement::try_from(b"ack".to_vec()).unwrap(),
+            ConnectionId::zero(),
+        );
+        let mut event = AbciEvent::try_from(write_ack.clone()).unwrap();
+        event.kind = "write_acknowledgement_packet".to_string();
+
+        assert_eq!(canonical_event_kind(&event.kind), WRITE_ACK_EVENT);
+        assert_eq!(canonical_event_kind(SEND_PACKET_EVENT), SEND_PACKET_EVENT);
+
+        let parsed = WriteAcknowledgement::try_from(event).unwrap();
+        assert_eq!(parsed, write_ack);
+        // Re-emission always uses the canonical kind.
+        assert_eq!(AbciEvent::try_from(parsed).unwrap().kind, WRITE_ACK_EVENT);
+    }
+
+    #[test]
+    fn send_packet_commitment_matches_known_vector() {
+        let expected: [u8; 32] = [
+            0xa9, 0x28, 0xb5, 0x1f, 0x62, 0xbd, 0x54, 0x00, 0x91, 0xec, 0x45, 0x1f, 0x4e, 0xf3,
+            0x45, 0x79, 0x4f, 0x05, 0x9e, 0x65, 0x91, 0x08, 0x16, 0x86, 0x61, 0x26, 0xdc, 0x36,
+            0x4f, 0x84, 0xcc, 0x15,
+        ];
+
+        let mut packet = mock_packet(1);
+        packet.data = b"packet data".to_vec();
+        packet.timeout_height_on_b = TimeoutHeight::At(Height::new(42, 24).unwrap());
+        packet.timeout_timestamp_on_b = Timestamp::from_nanoseconds(0x42).unwrap();
+        let event: AbciEvent = SendPacket::new(packet, Order::Unordered, ConnectionId::zero())
+            .try_into()
+            .unwrap();
+
+        let send_packet = SendPacket::try_from(event).unwrap();
+        assert_eq!(send_packet.packet_commitment().as_ref(), &expected[..]);
+    }
+
+    #[test]
+    fn truncated_plaintext_packet_data() {
+        let event = |plaintext: &str| {
+            let mut event = send_packet_event(1);
+            for attribute in &mut event.attributes {
+                if attribute.key_bytes() == PKT_DATA_ATTRIBUTE_KEY.as_bytes() {
+                    *attribute = (PKT_DATA_ATTRIBUTE_KEY, plaintext).into();
+                }
+            }
+            event
+        };
+
+        assert!(SendPacket::try_from(event("{\"amount\":\"100\"}")).is_ok());
+        assert!(matches!(
+            SendPacket::try_from(event("{\"amount\":")),
+            Err(ChannelError::TruncatedPlaintextData {
+                plaintext_len: 10,
+                hex_len: 16,
+            })
+        ));
+        assert!(matches!(
+            SendPacket::try_from(event("{\"amount\":\"200\"}")),
+            Err(ChannelError::PacketDataContentMismatch {
+                offset: 11,
+                plaintext_len: 16,
+                hex_len: 16,
+            })
+        ));
+
+        // The mismatch is reported regardless of the order of the attributes.
+        let mut reordered = event("{\"amount\":");
+        reordered.attributes.reverse();
+        assert!(matches!(
+            SendPacket::try_from(reordered),
+            Err(ChannelError::TruncatedPlaintextData { .. })
+        ));
+    }
+
+    #[test]
+    fn reconstruct_mock_open_init() {
+        let open_init = mock_open_init();
+        let event = AbciEvent::from(open_init.clone());
+
+        assert_eq!(event.kind, CHANNEL_OPEN_INIT_EVENT);
+        assert_eq!(OpenInit::try_from(event.attributes).unwrap(), open_init);
+        assert_eq!(open_init.port_id_on_a(), &PortId::transfer());
+        assert_eq!(open_init.conn_id_on_a(), &ConnectionId::zero());
+        assert_eq!(open_init.version_on_a().as_str(), "ics20-1");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn send_packet_attribute_map() {
+        let send_packet = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero());
+        let map = send_packet.attribute_map().unwrap();
+
+        assert_eq!(
+            map.keys().map(String::as_str).collect::<Vec<_>>(),
+            [
+                "packet_channel_ordering",
+                "packet_connection",
+                "packet_data",
+                "packet_data_hex",
+                "packet_dst_channel",
+                "packet_dst_port",
+                "packet_sequence",
+                "packet_src_channel",
+                "packet_src_port",
+                "packet_timeout_height",
+                "packet_timeout_timestamp",
+            ]
+        );
+        assert_eq!(map["packet_sequence"], "1");
+        assert_eq!(map["packet_src_channel"], "channel-0");
+
+        assert_eq!(
+            serde_json::to_string(&map).unwrap(),
+            r#"{"packet_channel_ordering":"ORDER_UNORDERED","packet_connection":"connection-0","packet_data":"{\"amount\":\"100\"}","packet_data_hex":"7b22616d6f756e74223a22313030227d","packet_dst_channel":"channel-1","packet_dst_port":"transfer","packet_sequence":"1","packet_src_channel":"channel-0","packet_src_port":"transfer","packet_timeout_height":"0-0","packet_timeout_timestamp":"1000"}"#
+        );
+    }
+
+    #[test]
+    fn id_attributes_as_str() {
+        let as_str = |attr: &dyn AsRef<str>| attr.as_ref().to_string();
+
+        assert_eq!(
+            as_str(&SrcPortIdAttribute::from(PortId::transfer())),
+            "transfer"
+        );
+        assert_eq!(
+            as_str(&SrcChannelIdAttribute::from(ChannelId::new(3))),
+            "channel-3"
+        );
+        assert_eq!(
+            as_str(&DstPortIdAttribute::from(PortId::transfer())),
+            "transfer"
+        );
+        assert_eq!(
+            as_str(&DstChannelIdAttribute::from(ChannelId::new(4))),
+            "channel-4"
+        );
+        assert_eq!(
+            as_str(&PortIdAttribute::from(PortId::transfer())),
+            "transfer"
+        );
+        assert_eq!(
+            as_str(&ChannelIdAttribute::from(ChannelId::zero())),
+            "channel-0"
+        );
+        assert_eq!(
+            as_str(&CounterpartyPortIdAttribute::from(PortId::transfer())),
+            "transfer"
+        );
+    }
+
+    #[test]
+    fn timeout_coherence() {
+        let timeout_height = TimeoutHeight::At(Height::new(4, 100).unwrap());
+        let timeout_timestamp = Timestamp::from_nanoseconds(1_000).unwrap();
+        let dst_height = Height::new(4, 50).unwrap();
+        let dst_time = Timestamp::from_nanoseconds(500).unwrap();
+
+        assert!(validate_timeout_coherence(
+            &timeout_height,
+            &timeout_timestamp,
+            dst_height,
+            dst_time
+        )
+        .is_ok());
+        assert!(validate_timeout_coherence(
+            &TimeoutHeight::Never,
+            &timeout_timestamp,
+            dst_height,
+            dst_time
+        )
+        .is_ok());
+        assert!(validate_timeout_coherence(
+            &timeout_height,
+            &Timestamp::none(),
+            dst_height,
+            dst_time
+        )
+        .is_ok());
+
+        assert!(matches!(
+            validate_timeout_coherence(
+                &timeout_height,
+                &timeout_timestamp,
+                Height::new(5, 50).unwrap(),
+                dst_time
+            ),
+            Err(ChannelError::TimeoutRevisionMismatch {
+                dst_revision: 5,
+                ..
+            })
+        ));
+        assert!(matches!(
+            validate_timeout_coherence(
+                &TimeoutHeight::Never,
+                &Timestamp::none(),
+                dst_height,
+                dst_time
+            ),
+            Err(ChannelError::MissingTimeout)
+        ));
+
+        // Either timeout elapsing is enough.
+        assert!(matches!(
+            validate_timeout_coherence(
+                &timeout_height,
+                &timeout_timestamp,
+                Height::new(4, 101).unwrap(),
+                dst_time
+            ),
+            Err(ChannelError::ElapsedTimeout { .. })
+        ));
+        assert!(matches!(
+            validate_timeout_coherence(
+                &timeout_height,
+                &timeout_timestamp,
+                dst_height,
+                Timestamp::from_nanoseconds(1_001).unwrap()
+            ),
+            Err(ChannelError::ElapsedTimeout { .. })
+        ));
+    }
+
+    #[test]
+    fn packet_timeout_is_elapsed() {
+        let timeout_height = TimeoutHeight::At(Height::new(0, 10).unwrap());
+        let timeout_timestamp = Timestamp::from_nanoseconds(1_000).unwrap();
+        let before = (
+            Height::new(0, 5).unwrap(),
+            Timestamp::from_nanoseconds(500).unwrap(),
+        );
+        let after = (
+            Height::new(0, 20).unwrap(),
+            Timestamp::from_nanoseconds(2_000).unwrap(),
+        );
+
+        let height_only = PacketTimeout::new(timeout_height, Timestamp::none());
+        assert!(!height_only.is_elapsed(before.0, before.1));
+        assert!(!height_only.is_elapsed(before.0, after.1));
+        assert!(height_only.is_elapsed(after.0, before.1));
+
+        let timestamp_only = PacketTimeout::new(TimeoutHeight::Never, timeout_timestamp);
+        assert!(!timestamp_only.is_elapsed(before.0, before.1));
+        assert!(!timestamp_only.is_elapsed(after.0, before.1));
+        assert!(timestamp_only.is_elapsed(before.0, after.1));
+
+        let both = PacketTimeout::new(timeout_height, timeout_timestamp);
+        assert!(!both.is_elapsed(before.0, before.1));
+        assert!(both.is_elapsed(after.0, before.1));
+        assert!(both.is_elapsed(before.0, after.1));
+
+        let never = PacketTimeout::new(TimeoutHeight::Never, Timestamp::none());
+        assert!(!never.is_elapsed(after.0, after.1));
+    }
+
+    #[test]
+    fn whitespace_padded_numeric_and_id_values() {
+        let channel_id_attr: abci::EventAttribute = ("channel_id", " channel-0 ").into();
+        assert_eq!(
+            ChannelIdAttribute::try_from(channel_id_attr)
+                .unwrap()
+                .channel_id,
+            ChannelId::zero()
+        );
+
+        let seq_attr: abci::EventAttribute = ("packet_sequence", "42\n").into();
+        assert_eq!(
+            SequenceAttribute::try_from(seq_attr).unwrap().sequence,
+            Sequence::from(42)
+        );
+
+        let timeout_attr: abci::EventAttribute = ("packet_timeout_height", "\t1-10\n").into();
+        assert_eq!(
+            TimeoutHeightAttribute::try_from(timeout_attr)
+                .unwrap()
+                .timeout_height,
+            TimeoutHeight::At(Height::new(1, 10).unwrap())
+        );
+
+        // Packet data and versions are kept byte-exact.
+        let data_attr: abci::EventAttribute = ("packet_data", "data\n").into();
+        assert_eq!(
+            PacketDataAttribute::try_from(data_attr)
+                .unwrap()
+                .packet_data,
+            b"data\n".to_vec()
+        );
+        let version_attr: abci::EventAttribute = ("version", " ics20-1").into();
+        assert_eq!(
+            VersionAttribute::try_from(version_attr)
+                .unwrap()
+                .version
+                .as_str(),
+            " ics20-1"
+        );
+    }
+
+    #[test]
+    fn exported_attribute_keys() {
+        assert_eq!(keys::PKT_SEQ, "packet_sequence");
+        assert_eq!(keys::PKT_DATA_HEX, "packet_data_hex");
+        assert_eq!(keys::PKT_CONNECTION_ID, "packet_connection");
+        assert_eq!(keys::CHANNEL_ID, "channel_id");
+        assert_eq!(keys::COUNTERPARTY_PORT_ID, "counterparty_port_id");
+
+        let seq_attr: abci::EventAttribute = SequenceAttribute::from(Sequence::from(1)).into();
+        assert_eq!(seq_attr.key_str().unwrap(), keys::PKT_SEQ);
+    }
+
+    #[test]
+    fn to_abci_events_reports_failing_index() {
+        let send_packet =
+            |seq| SendPacket::new(mock_packet(seq), Order::Unordered, ConnectionId::zero());
+
+        let events = to_abci_events([send_packet(1), send_packet(2)]).unwrap();
+        assert_eq!(events.len(), 2);
+        for (event, seq) in events.iter().zip([1, 2]) {
+            assert_eq!(event.kind, SEND_PACKET_EVENT);
+            assert_eq!(
+                event.attributes,
+                send_packet(seq).canonical_attributes().unwrap()
+            );
+        }
+
+        let mut non_utf8 = mock_packet(2);
+        non_utf8.data = vec![0xff, 0xfe];
+        let packets = [
+            send_packet(1),
+            SendPacket::new(non_utf8, Order::Unordered, ConnectionId::zero()),
+            send_packet(3),
+        ];
+
+        match to_abci_events(packets) {
+            Err(ChannelError::EventConversionFailed { index, error }) => {
+                assert_eq!(index, 1);
+                assert!(matches!(*error, ChannelError::NonUtf8PacketData));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn timeout_height_semantic_eq() {
+        let never = TimeoutHeightAttribute::never();
+        let parsed =
+            TimeoutHeightAttribute::try_from((&b"packet_timeout_height"[..], &b"0-0"[..])).unwrap();
+        let at = TimeoutHeightAttribute::from(Height::new(0, 5).unwrap());
+
+        assert!(parsed.semantic_eq(&never));
+        assert!(!at.semantic_eq(&never));
+        assert!(at.semantic_eq(&at));
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn zero_timeout_height_semantically_equals_never() {
+        use borsh::BorshDeserialize;
+
+        let zero = Height::try_from_slice(&[0; 16]).unwrap();
+        let explicit = TimeoutHeightAttribute::from(zero);
+        let never = TimeoutHeightAttribute::never();
+
+        assert_ne!(explicit, never);
+        assert!(explicit.semantic_eq(&never));
+        assert!(never.semantic_eq(&explicit));
+    }
+
+    #[test]
+    fn subscription_predicates_of_send_packet() {
+        assert_eq!(
+            subscription_predicates(&PortId::transfer(), &ChannelId::zero(), SEND_PACKET_EVENT),
+            Some(vec![
+                (
+                    "send_packet.packet_src_port".to_string(),
+                    "transfer".to_string()
+                ),
+                (
+                    "send_packet.packet_src_channel".to_string(),
+                    "channel-0".to_string()
+                ),
+            ])
+        );
+        assert_eq!(
+            subscription_predicates(&PortId::transfer(), &ChannelId::zero(), RECV_PACKET_EVENT)
+                .unwrap()[1]
+                .0,
+            "recv_packet.packet_dst_channel"
+        );
+        assert_eq!(
+            subscription_predicates(&PortId::transfer(), &ChannelId::zero(), "coin_spent"),
+            None
+        );
+    }
+
+    #[test]
+    fn acknowledgement_attribute_new() {
+        assert!(matches!(
+            AcknowledgementAttribute::new(Vec::new()),
+            Err(ChannelError::EmptyAcknowledgement)
+        ));
+
+        let attr = AcknowledgementAttribute::new(b"{\"result\":\"AQ==\"}".to_vec()).unwrap();
+        assert_eq!(attr.acknowledgement.as_bytes(), b"{\"result\":\"AQ==\"}");
+
+        let unchecked = AcknowledgementAttribute::from(Acknowledgement::new_unchecked(Vec::new()));
+        assert!(matches!(
+            unchecked.validate(),
+            Err(ChannelError::EmptyAcknowledgement)
+        ));
+    }
+
+    #[cfg(feature = "base64-attributes")]
+    #[test]
+    fn parse_base64_packet_data() {
+        let base64_only = |event: AbciEvent| {
+            let attributes = event
+                .attributes
+                .into_iter()
+                .filter(|attr| {
+                    !matches!(
+                        attr.key_str(),
+                        Ok(PKT_DATA_ATTRIBUTE_KEY | PKT_DATA_HEX_ATTRIBUTE_KEY)
+                    )
+                })
+                .chain([("packet_data_b64", "eyJhbW91bnQiOiIxMDAifQ==").into()])
+                .collect();
+            AbciEvent {
+                attributes,
+                ..event
+            }
+        };
+
+        let event = base64_only(send_packet_event(1));
+        let send_packet = SendPacket::try_from(event).unwrap();
+        assert_eq!(send_packet.packet_data(), b"{\"amount\":\"100\"}");
+        assert!(send_packet.extra_attributes().is_empty());
+
+        let mut event = send_packet_event(1);
+        event
+            .attributes
+            .push(("packet_data_b64", "eyJhbW91bnQiOiIxMDAifQ==").into());
+        assert!(SendPacket::try_from(event).is_ok());
+
+        let mut event = send_packet_event(1);
+        event
+            .attributes
+            .push(("packet_data_b64", "b3RoZXI=").into());
+        assert!(matches!(
+            SendPacket::try_from(event),
+            Err(ChannelError::PacketDataContentMismatch { offset: 0, .. })
+        ));
+
+        let mut event = base64_only(send_packet_event(1));
+        event.attributes.pop();
+        event
+            .attributes
+            .push(("packet_data_b64", "not base64!").into());
+        assert!(matches!(
+            SendPacket::try_from(event),
+            Err(ChannelError::InvalidAttributeValue { .. })
+        ));
+    }
+
+    #[test]
+    fn attribute_defaults() {
+        assert_eq!(
+            TimeoutHeightAttribute::default().timeout_height,
+            TimeoutHeight::Never
+        );
+        assert_eq!(
+            TimeoutTimestampAttribute::default().timeout_timestamp,
+            Timestamp::none()
+        );
+        assert_eq!(SequenceAttribute::default().sequence, Sequence::from(1));
+        assert_eq!(
+            PacketTimeout::default(),
+            PacketTimeout::new(TimeoutHeight::Never, Timestamp::none())
+        );
+    }
+
+    #[test]
+    fn handshake_events_from_borrowed_attributes() {
+        let open_init = mock_open_init();
+        let event = AbciEvent::from(open_init.clone());
+        assert_eq!(
+            OpenInit::try_from_slice(&event.attributes).unwrap(),
+            open_init
+        );
+
+        let open_confirm = OpenConfirm::new(
+            PortId::transfer(),
+            ChannelId::new(1),
+            PortId::transfer(),
+            ChannelId::zero(),
+            ConnectionId::new(1),
+        );
+        let event = AbciEvent::from(open_confirm.clone());
+        assert_eq!(
+            OpenConfirm::try_from_slice(&event.attributes).unwrap(),
+            open_confirm
+        );
+        assert_eq!(
+            ChannelEndpoint::try_from_slice(&event.attributes).unwrap(),
+            ChannelEndpoint {
+                connection_id: ConnectionId::new(1),
+                port_id: PortId::transfer(),
+                channel_id: ChannelId::new(1),
+            }
+        );
+        // The borrowed attributes are left available to the caller.
+        assert_eq!(
+            OpenConfirm::try_from(event.attributes).unwrap(),
+            open_confirm
+        );
+    }
+
+    #[test]
+    fn timeout_timestamp_in_seconds() {
+        let attr = TimeoutTimestampAttribute::from(
+            Timestamp::from_nanoseconds(1_700_000_000_999_999_999).unwrap(),
+        );
+
+        let nanoseconds = abci::EventAttribute::from(attr.clone());
+        assert_eq!(nanoseconds.value_str().unwrap(), "1700000000999999999");
+
+        let seconds = attr.into_attribute_seconds();
+        assert_eq!(seconds.key_str().unwrap(), "packet_timeout_timestamp");
+        assert_eq!(seconds.value_str().unwrap(), "1700000000");
+
+        assert_eq!(
+            TimeoutTimestampAttribute::default()
+                .into_attribute_seconds()
+                .value_str()
+                .unwrap(),
+            "0"
+        );
+    }
+
+    #[test]
+    fn reconcile_packet_events() {
+        let mut conflicting = mock_packet(3);
+        conflicting.data = b"{\"amount\":\"999\"}".to_vec();
+        let conflicting_event: AbciEvent =
+            SendPacket::new(conflicting, Order::Unordered, ConnectionId::zero())
+                .try_into()
+                .unwrap();
+
+        let a = vec![
+            send_packet_event(1),
+            send_packet_event(2),
+            send_packet_event(3),
+        ];
+        let b = vec![
+            send_packet_event(2),
+            conflicting_event.clone(),
+            send_packet_event(4),
+        ];
+
+        let diff = reconcile(&a, &b);
+        let seqs = |packets: &[SendPacket]| {
+            packets
+                .iter()
+                .map(|packet| u64::from(*packet.seq_on_a()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(seqs(&diff.only_in_a), vec![1]);
+        assert_eq!(seqs(&diff.only_in_b), vec![4]);
+        assert_eq!(diff.conflicting.len(), 1);
+        assert_eq!(
+            diff.conflicting[0],
+            (
+                SendPacket::try_from(send_packet_event(3)).unwrap(),
+                SendPacket::try_from(conflicting_event).unwrap()
+            )
+        );
+
+        assert!(reconcile(&a, &a).is_empty());
+        assert!(reconcile(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn id_attributes_from_ids() {
+        let src_port: SrcPortIdAttribute = PortId::transfer().into();
+        let src_channel: SrcChannelIdAttribute = ChannelId::new(1).into();
+        let dst_port: DstPortIdAttribute = PortId::transfer().into();
+        let dst_channel: DstChannelIdAttribute = ChannelId::new(2).into();
+        let packet_connection: PacketConnectionIdAttribute = ConnectionId::new(3).into();
+        assert_eq!(src_port.src_port_id, PortId::transfer());
+        assert_eq!(src_channel.src_channel_id, ChannelId::new(1));
+        assert_eq!(dst_port.dst_port_id, PortId::transfer());
+        assert_eq!(dst_channel.dst_channel_id, ChannelId::new(2));
+        assert_eq!(packet_connection.connection_id, ConnectionId::new(3));
+
+        let port: PortIdAttribute = PortId::transfer().into();
+        let channel: ChannelIdAttribute = ChannelId::new(4).into();
+        let counterparty_port: CounterpartyPortIdAttribute = PortId::transfer().into();
+        let counterparty_channel: CounterpartyChannelIdAttribute = ChannelId::new(5).into();
+        let connection: ConnectionIdAttribute = ConnectionId::new(6).into();
+        assert_eq!(port.port_id, PortId::transfer());
+        assert_eq!(channel.channel_id, ChannelId::new(4));
+        assert_eq!(counterparty_port.counterparty_port_id, PortId::transfer());
+        assert_eq!(
+            counterparty_channel.counterparty_channel_id,
+            ChannelId::new(5)
+        );
+        assert_eq!(connection.connection_id, ConnectionId::new(6));
+    }
+
+    #[test]
+    fn describe_events() {
+        let mut packet = mock_packet(5);
+        packet.timeout_height_on_b = TimeoutHeight::At(Height::new(3, 1000).unwrap());
+        let event: AbciEvent = SendPacket::new(packet, Order::Unordered, ConnectionId::zero())
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            describe(&event),
+            "SendPacket seq=5 channel-0->channel-1 timeout=3-1000"
+        );
+
+        let mut malformed = send_packet_event(5);
+        malformed
+            .attributes
+            .retain(|attr| attr.key_str().unwrap() != "packet_sequence");
+        assert_eq!(
+            describe(&malformed),
+            "SendPacket seq=? channel-0->channel-1 timeout=never"
+        );
+
+        assert_eq!(
+            describe(&AbciEvent::from(mock_open_init())),
+            "ChannelOpenInit transfer/channel-0->transfer/? connection=connection-0"
+        );
+
+        let unknown = AbciEvent {
+            kind: "coin_spent".to_string(),
+            attributes: vec![
+                ("spender", "cosmos1abc").into(),
+                ("amount", "100stake").into(),
+            ],
+        };
+        assert_eq!(
+            describe(&unknown),
+            "coin_spent spender=cosmos1abc amount=100stake"
+        );
+    }
+
+    #[test]
+    fn verify_channel_ordering() {
+        let attr = ChannelOrderingAttribute::from(Order::Ordered);
+
+        assert!(attr.verify_ordering(Order::Ordered).is_ok());
+        assert!(matches!(
+            attr.verify_ordering(Order::Unordered),
+            Err(ChannelError::ChannelEndOrderingMismatch {
+                expected: Order::Unordered,
+                actual: Order::Ordered,
+            })
+        ));
+    }
+
+    #[test]
+    fn packet_data_hex_casing() {
+        let hex_value = |attributes: Vec<abci::EventAttribute>| {
+            attributes
+                .into_iter()
+                .find(|attr| attr.key_str().unwrap() == "packet_data_hex")
+                .unwrap()
+                .value_str()
+                .unwrap()
+                .to_string()
+        };
+
+        let attr = PacketDataAttribute::from(b"{\"key\":\"\xc3\xbf\"}".to_vec());
+        let lowercase = hex_value(attr.clone().try_into().unwrap());
+        assert_eq!(lowercase, "7b226b6579223a22c3bf227d");
+        assert_eq!(
+            hex_value(
+                attr.clone()
+                    .into_attributes_with_hex_case(HexCase::Lower)
+                    .unwrap()
+            ),
+            lowercase
+        );
+        assert_eq!(
+            hex_value(attr.into_attributes_with_hex_case(HexCase::Upper).unwrap()),
+            "7B226B6579223A22C3BF227D"
+        );
+        assert_eq!(HexCase::default(), HexCase::Lower);
+    }
+
+    #[test]
+    fn send_packet_at_height() {
+        let height = Height::new(1, 42).unwrap();
+        let send_packet = SendPacketAtHeight::parse(send_packet_event(1), height).unwrap();
+
+        assert_eq!(send_packet.height(), height);
+        assert_eq!(send_packet.min_client_height(), Height::new(1, 43).unwrap());
+        assert_eq!(
+            send_packet.into_event(),
+            SendPacket::try_from(send_packet_event(1)).unwrap()
+        );
+
+        let mut malformed = send_packet_event(1);
+        malformed.kind = RECV_PACKET_EVENT.to_string();
+        assert!(SendPacketAtHeight::parse(malformed, height).is_err());
+    }
+
+    #[test]
+    fn parse_send_packet_leniently() {
+        let mut event = send_packet_event(1);
+        let timeout_timestamp = event
+            .attributes
+            .iter_mut()
+            .find(|attr| attr.key_bytes() == PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY.as_bytes())
+            .unwrap();
+        *timeout_timestamp = (PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY, "soon").into();
+
+        let (partial, errors) = parse_lenient(&event);
+
+        assert_eq!(errors.len(), 1);
+        assert!(SendPacket::try_from(event).is_err());
+        assert_eq!(partial.timeout_timestamp_on_b, None);
+        assert_eq!(partial.seq_on_a, Some(Sequence::from(1)));
+        assert_eq!(
+            partial.packet_data.as_deref(),
+            Some(&b"{\"amount\":\"100\"}"[..])
+        );
+        assert_eq!(partial.chan_id_on_b, Some(ChannelId::new(1)));
+        assert_eq!(partial.timeout_height_on_b, Some(TimeoutHeight::Never));
+    }
+
+    #[test]
+    fn parse_send_packet_leniently_reports_missing_attributes() {
+        let mut event = send_packet_event(1);
+        event
+            .attributes
+            .retain(|attr| attr.key_bytes() != PKT_CONNECTION_ID_ATTRIBUTE_KEY.as_bytes());
+
+        let (partial, errors) = parse_lenient(&event);
+
+        assert!(matches!(
+            errors.as_slice(),
+            [ChannelError::MissingAttributeKey { attribute_key }]
+                if attribute_key == PKT_CONNECTION_ID_ATTRIBUTE_KEY
+        ));
+        assert_eq!(partial.conn_id_on_a, None);
+        assert_eq!(partial.seq_on_a, Some(Sequence::from(1)));
+
+        event.kind = RECV_PACKET_EVENT.to_string();
+        let (partial, errors) = parse_lenient(&event);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(partial, PartialSendPacket::default());
+    }
+
+    fn binary_write_ack() -> WriteAcknowledgement {
+        WriteAcknowledgement::new(
+            mock_packet(1),
+            Acknowledgement::try_from(vec![0x01, 0xff, 0xfe]).unwrap(),
+            ConnectionId::zero(),
+        )
+    }
+
+    #[cfg(feature = "skip-deprecated-ack")]
+    #[test]
+    fn emit_binary_acknowledgement() {
+        let write_ack = binary_write_ack();
+        let event = AbciEvent::try_from(write_ack.clone()).unwrap();
+
+        assert!(!event
+            .attributes
+            .iter()
+            .any(|attr| attr.key_bytes() == keys::PKT_ACK.as_bytes()));
+        assert!(event.attributes.iter().any(|attr| {
+            attr.key_bytes() == PKT_ACK_HEX_ATTRIBUTE_KEY.as_bytes()
+                && attr.value_bytes() == b"01fffe"
+        }));
+
+        let parsed = WriteAcknowledgement::try_from(event).unwrap();
+        assert_eq!(parsed, write_ack);
+        assert_eq!(parsed.acknowledgement().as_bytes(), &[0x01, 0xff, 0xfe]);
+
+        // UTF-8 acknowledgements still carry the deprecated attribute.
+        let attributes = Vec::<abci::EventAttribute>::try_from(AcknowledgementAttribute::from(
+            Acknowledgement::try_from(b"ack".to_vec()).unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(attributes.len(), 2);
+    }
+
+    #[cfg(not(feature = "skip-deprecated-ack"))]
+    #[test]
+    fn emit_binary_acknowledgement() {
+        assert!(matches!(
+            AbciEvent::try_from(binary_write_ack()),
+            Err(ChannelError::NonUtf8PacketData)
+        ));
+    }
+
+    #[test]
+    fn packet_data_length_mismatch() {
+        let hex = PacketDataAttribute::from(b"{\"amount\":\"100\"}".to_vec());
+
+        let oversized = PacketDataAttribute::from(b"{\"amount\":\"100\"}\n".to_vec());
+        assert!(matches!(
+            oversized.check_consistent_with(&hex),
+            Err(ChannelError::OversizedPlaintextData {
+                plaintext_len: 17,
+                hex_len: 16,
+            })
+        ));
+
+        let truncated = PacketDataAttribute::from(b"{\"amount\"".to_vec());
+        assert!(matches!(
+            truncated.check_consistent_with(&hex),
+            Err(ChannelError::TruncatedPlaintextData {
+                plaintext_len: 9,
+                hex_len: 16,
+            })
+        ));
+    }
+
+    #[test]
+    fn packet_data_single_byte_mismatch() {
+        let hex = PacketDataAttribute::from(b"{\"amount\":\"100\"}".to_vec());
+        let plaintext = PacketDataAttribute::from(b"{\"amount\":\"101\"}".to_vec());
+
+        assert!(matches!(
+            plaintext.check_consistent_with(&hex),
+            Err(ChannelError::PacketDataContentMismatch {
+                offset: 13,
+                plaintext_len: 16,
+                hex_len: 16,
+            })
+        ));
+        assert!(hex.check_consistent_with(&hex).is_ok());
+
+        // A content difference is reported even if the lengths differ too.
+        let plaintext = PacketDataAttribute::from(b"{\"amount\":\"2\"}".to_vec());
+        assert!(matches!(
+            plaintext.check_consistent_with(&hex),
+            Err(ChannelError::PacketDataContentMismatch {
+                offset: 11,
+                plaintext_len: 14,
+                hex_len: 16,
+            })
+        ));
+    }
+
+    #[test]
+    fn collect_packet_attributes() {
+        let packet = mock_packet(1);
+        let packet_attributes: Vec<PacketAttribute> = vec![
+            PacketConnectionIdAttribute::from(ConnectionId::zero()).into(),
+            SequenceAttribute::from(packet.seq_on_a).into(),
+            DstChannelIdAttribute::from(packet.chan_id_on_b.clone()).into(),
+            PacketDataAttribute::from(packet.data.clone()).into(),
+            ChannelOrderingAttribute::from(Order::Unordered).into(),
+            TimeoutTimestampAttribute::from(packet.timeout_timestamp_on_b).into(),
+            SrcChannelIdAttribute::from(packet.chan_id_on_a.clone()).into(),
+            DstPortIdAttribute::from(packet.port_id_on_b.clone()).into(),
+            TimeoutHeightAttribute::from(packet.timeout_height_on_b).into(),
+            SrcPortIdAttribute::from(packet.port_id_on_a.clone()).into(),
+        ];
+
+        let attributes =
+            Vec::try_from(packet_attributes.into_iter().collect::<PacketAttributes>()).unwrap();
+
+        let expected = SendPacket::new(packet, Order::Unordered, ConnectionId::zero())
+            .canonical_attributes()
+            .unwrap();
+        assert_eq!(attributes, expected);
+
+        // Emission follows the rules of the individual attributes.
+        let binary_data: PacketAttributes = [
+            PacketAttribute::from(SequenceAttribute::from(Sequence::from(1))),
+            PacketDataAttribute::from(vec![0xff]).into(),
+        ]
+        .into_iter()
+        .collect();
+        assert!(matches!(
+            Vec::<abci::EventAttribute>::try_from(binary_data),
+            Err(ChannelError::NonUtf8PacketData)
+        ));
+
+        let empty_ack: PacketAttributes = [PacketAttribute::from(AcknowledgementAttribute::from(
+            Acknowledgement::new_unchecked(Vec::new()),
+        ))]
+        .into_iter()
+        .collect();
+        assert!(matches!(
+            Vec::<abci::EventAttribute>::try_from(empty_ack),
+            Err(ChannelError::EmptyAcknowledgement)
+        ));
+    }
+
+    #[test]
+    fn parse_revision_zero_timeout_height() {
+        let attr = TimeoutHeightAttribute::try_from((&b"packet_timeout_height"[..], &b"0-100"[..]))
+            .unwrap();
+        assert_eq!(
+            attr.timeout_height,
+            TimeoutHeight::At(Height::new(0, 100).unwrap())
+        );
+        assert_eq!(
+            abci::EventAttribute::from(attr).value_str().unwrap(),
+            "0-100"
+        );
+
+        assert!(
+            TimeoutHeightAttribute::try_from((&b"packet_timeout_height"[..], &b"1-0"[..])).is_err()
+        );
+    }
+
+    #[test]
+    fn module_attribute_round_trip() {
+        let attr: abci::EventAttribute = ModuleAttribute::from("transfer".to_string()).into();
+        assert_eq!(attr.key_str().unwrap(), keys::MODULE);
+        assert_eq!(attr.value_str().unwrap(), "transfer");
+        assert_eq!(ModuleAttribute::try_from(attr).unwrap().module, "transfer");
+
+        let send_packet = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero())
+            .with_module("transfer".to_string());
+        let event = AbciEvent::try_from(send_packet.clone()).unwrap();
+        let parsed = SendPacket::try_from(event).unwrap();
+        assert_eq!(parsed.module(), Some("transfer"));
+        assert!(parsed.extra_attributes().is_empty());
+        assert_eq!(parsed, send_packet);
+
+        let open_init = mock_open_init().with_module("transfer".to_string());
+        let event = AbciEvent::from(open_init.clone());
+        assert_eq!(OpenInit::try_from(event.attributes).unwrap(), open_init);
+
+        // Events without the attribute still parse.
+        let event = AbciEvent::from(mock_open_init());
+        assert_eq!(OpenInit::try_from(event.attributes).unwrap().module(), None);
+    }
+
+    #[cfg(any(feature = "borsh", feature = "parity-scale-codec"))]
+    fn send_packet_with_extras() -> SendPacket {
+        SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero())
+            .with_module("transfer".to_string())
+            .with_extra_attributes(vec![
+                ("packet_fee", "100stake", true).into(),
+                abci::EventAttribute::V034(abci::v0_34::EventAttribute {
+                    key: b"relayer".to_vec(),
+                    value: vec![0xff, 0xfe],
+                    index: false,
+                }),
+            ])
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh_round_trip_keeps_extra_attributes() {
+        let send_packet = send_packet_with_extras();
+
+        let bytes = borsh::to_vec(&send_packet).unwrap();
+        assert_eq!(
+            borsh::from_slice::<SendPacket>(&bytes).unwrap(),
+            send_packet
+        );
+
+        let open_init = mock_open_init().with_module("transfer".to_string());
+        let bytes = borsh::to_vec(&open_init).unwrap();
+        assert_eq!(borsh::from_slice::<OpenInit>(&bytes).unwrap(), open_init);
+    }
+
+    #[cfg(feature = "parity-scale-codec")]
+    #[test]
+    fn scale_round_trip_keeps_extra_attributes() {
+        use parity_scale_codec::{Decode, Encode};
+
+        let send_packet = send_packet_with_extras();
+
+        let bytes = send_packet.encode();
+        assert_eq!(
+            SendPacket::decode(&mut bytes.as_slice()).unwrap(),
+            send_packet
+        );
+
+        let open_init = mock_open_init().with_module("transfer".to_string());
+        let bytes = open_init.encode();
+        assert_eq!(OpenInit::decode(&mut bytes.as_slice()).unwrap(), open_init);
+    }
+
+    #[test]
+    fn connection_id_of_events() {
+        assert_eq!(
+            connection_id_of(&send_packet_event(1)),
+            Some(ConnectionId::zero())
+        );
+
+        let open_try = OpenTry::new(
+            PortId::transfer(),
+            ChannelId::new(1),
+            PortId::transfer(),
+            ChannelId::zero(),
+            ConnectionId::new(7),
+            Version::new("ics20-1".to_string()),
+        );
+        assert_eq!(
+            connection_id_of(&open_try.into()),
+            Some(ConnectionId::new(7))
+        );
+
+        let message = AbciEvent {
+            kind: "message".to_string(),
+            attributes: vec![("module", "ibc_channel").into()],
+        };
+        assert_eq!(connection_id_of(&message), None);
+
+        let mut malformed = send_packet_event(1);
+        malformed
+            .attributes
+            .retain(|attr| attr.key_bytes() != PKT_CONNECTION_ID_ATTRIBUTE_KEY.as_bytes());
+        malformed
+            .attributes
+            .push((PKT_CONNECTION_ID_ATTRIBUTE_KEY, "not a connection").into());
+        assert_eq!(connection_id_of(&malformed), None);
+    }
+
+    #[test]
+    fn parse_timeout_packet_requires_a_timeout() {
+        let timeout_event = |timeout_height, timeout_timestamp| {
+            let mut packet = mock_packet(1);
+            packet.timeout_height_on_b = timeout_height;
+            packet.timeout_timestamp_on_b = timeout_timestamp;
+            AbciEvent::try_from(TimeoutPacket::new(packet, Order::Unordered)).unwrap()
+        };
+        let timeout_height = TimeoutHeight::At(Height::new(0, 100).unwrap());
+        let timeout_timestamp = Timestamp::from_nanoseconds(1_000).unwrap();
+
+        let height_only = timeout_event(timeout_height, Timestamp::none());
+        assert_eq!(
+            TimeoutPacket::try_from(height_only)
+                .unwrap()
+                .timeout_height_on_b(),
+            &timeout_height
+        );
+
+        let timestamp_only = timeout_event(TimeoutHeight::Never, timeout_timestamp);
+        assert_eq!(
+            TimeoutPacket::try_from(timestamp_only)
+                .unwrap()
+                .timeout_timestamp_on_b(),
+            &timeout_timestamp
+        );
+
+        let both_absent = timeout_event(TimeoutHeight::Never, Timestamp::none());
+        assert!(matches!(
+            TimeoutPacket::try_from(both_absent),
+            Err(ChannelError::MissingTimeout)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn versioned_send_packet() {
+        let send_packet = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero())
+            .with_module("transfer".to_string());
+
+        let json = serde_json::to_string(&send_packet.to_versioned().unwrap()).unwrap();
+        assert!(json.starts_with(r#"{"version":"1","#));
+        let versioned: VersionedSendPacket = serde_json::from_str(&json).unwrap();
+        assert_eq!(SendPacket::from_versioned(versioned).unwrap(), send_packet);
+
+        // A document without the optional `module`, and holding a field added
+        // by a later version, still deserializes.
+        let v1 = concat!(
+            r#"{"version":"1","packet_data_hex":"7b22616d6f756e74223a22313030227d","#,
+            r#""packet_timeout_height":"0-0","packet_timeout_timestamp":"1000","#,
+            r#""packet_sequence":"1","packet_src_port":"transfer","#,
+            r#""packet_src_channel":"channel-0","packet_dst_port":"transfer","#,
+            r#""packet_dst_channel":"channel-1","#,
+            r#""packet_channel_ordering":"ORDER_UNORDERED","#,
+            r#""packet_connection":"connection-0","packet_fee":"10uatom"}"#
+        );
+        let versioned: VersionedSendPacket = serde_json::from_str(v1).unwrap();
+        let parsed = SendPacket::from_versioned(versioned).unwrap();
+        assert_eq!(parsed.module(), None);
+        assert!(parsed.eq_ignoring_deprecated(&send_packet));
+
+        assert!(serde_json::from_str::<VersionedSendPacket>(
+            &v1.replace(r#""version":"1""#, r#""version":"0""#)
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn versioned_send_packet_written_by_later_version() {
+        let send_packet = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero())
+            .with_module("transfer".to_string());
+
+        // A v1 document as written by a later version of this crate, which
+        // added an optional field.
+        let json = serde_json::to_string(&send_packet.to_versioned().unwrap()).unwrap();
+        let json = json.replacen('}', r#","packet_memo":"memo"}"#, 1);
+
+        let versioned: VersionedSendPacket = serde_json::from_str(&json).unwrap();
+        assert_eq!(SendPacket::from_versioned(versioned).unwrap(), send_packet);
+    }
+
+    #[test]
+    fn parse_packet_event_requiring_indexed_key_attributes() {
+        let emitted = send_packet_event(1);
+        assert!(emitted.attributes.iter().all(|attr| !attr.index()));
+        assert!(matches!(
+            SendPacket::try_from_indexed(emitted.clone()),
+            Err(ChannelError::NonIndexedAttribute { .. })
+        ));
+
+        let indexed = index_key_attributes(emitted);
+        for attribute in &indexed.attributes {
+            let key = attribute.key_str().unwrap();
+            let is_key_attribute = [
+                "packet_sequence",
+                "packet_src_channel",
+                "packet_dst_channel",
+            ]
+            .contains(&key);
+            assert_eq!(attribute.index(), is_key_attribute, "{key}");
+        }
+        assert!(SendPacket::try_from_indexed(indexed.clone()).is_ok());
+
+        let mut non_indexed = indexed;
+        for attribute in &mut non_indexed.attributes {
+            if attribute.key_str().unwrap() == "packet_sequence" {
+                *attribute = ("packet_sequence", "1", false).into();
+            }
+        }
+        assert!(SendPacket::try_from(non_indexed.clone()).is_ok());
+        assert!(matches!(
+            SendPacket::try_from_indexed(non_indexed),
+            Err(ChannelError::NonIndexedAttribute { attribute_key }) if attribute_key == "packet_sequence"
+        ));
+    }
+
+    #[test]
+    fn channel_id_attribute_numeric_suffix() {
+        let src = |value: &str| -> SrcChannelIdAttribute {
+            SrcChannelIdAttribute::try_from(abci::EventAttribute::from((
+                "packet_src_channel",
+                value,
+            )))
+            .unwrap()
+        };
+
+        assert_eq!(src("channel-0").numeric_suffix(), Some(0));
+        assert_eq!(src("channel-123").numeric_suffix(), Some(123));
+
+        let dst = DstChannelIdAttribute::from(ChannelId::new(7));
+        assert_eq!(dst.numeric_suffix(), Some(7));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn channel_id_attribute_numeric_suffix_of_unvalidated_id() {
+        // Deserialization skips the validation that rejects leading zeros.
+        let src: SrcChannelIdAttribute =
+            serde_json::from_str(r#"{"src_channel_id":"channel-007"}"#).unwrap();
+        assert_eq!(src.numeric_suffix(), None);
+
+        let dst: DstChannelIdAttribute =
+            serde_json::from_str(r#"{"dst_channel_id":"channel-007"}"#).unwrap();
+        assert_eq!(dst.numeric_suffix(), None);
+    }
+
+    #[test]
+    fn send_packet_event_hash() {
+        let dual_attr_event = send_packet_event(1);
+        let mut hex_only_event = dual_attr_event.clone();
+        hex_only_event.attributes.retain(|attr| {
+            attr.key_str()
+                .map_or(true, |key| key != PKT_DATA_ATTRIBUTE_KEY)
+        });
+
+        let dual_attr = SendPacket::try_from(dual_attr_event).unwrap();
+        let hex_only = SendPacket::try_from(hex_only_event).unwrap();
+        assert_eq!(dual_attr.event_hash(), hex_only.event_hash());
+
+        let other = SendPacket::try_from(send_packet_event(2)).unwrap();
+        assert_ne!(dual_attr.event_hash(), other.event_hash());
+    }
+
+    #[test]
+    fn emit_attributes_from_borrows() {
+        let src_port = SrcPortIdAttribute::from(PortId::transfer());
+        let attribute: abci::EventAttribute = (&src_port).into();
+        assert_eq!(attribute.key_str().unwrap(), "packet_src_port");
+        assert_eq!(attribute.value_str().unwrap(), "transfer");
+        assert_eq!(src_port.to_attribute(), attribute);
+        assert_eq!(abci::EventAttribute::from(src_port), attribute);
+
+        let dst_channel = DstChannelIdAttribute::from(ChannelId::new(7));
+        let attribute = abci::EventAttribute::from(&dst_channel);
+        assert_eq!(attribute.value_str().unwrap(), "channel-7");
+        assert!(!attribute.index());
+        assert_eq!(dst_channel.to_attribute(), attribute);
+
+        let channel = ChannelIdAttribute {
+            channel_id: ChannelId::new(3),
+        };
+        let attribute = abci::EventAttribute::from(&channel);
+        assert_eq!(attribute.key_str().unwrap(), "channel_id");
+        assert_eq!(abci::EventAttribute::from(channel), attribute);
+    }
+
+    #[test]
+    fn open_init_rejects_counterparty_channel_id() {
+        let open_init = mock_open_init();
+        let event = AbciEvent::from(open_init.clone());
+        assert_eq!(
+            OpenInit::try_from_slice(&event.attributes).unwrap(),
+            open_init
+        );
+
+        let mut without_counterparty = event.attributes.clone();
+        without_counterparty
+            .retain(|attr| attr.key_str().unwrap() != COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY);
+        assert_eq!(OpenInit::try_from(without_counterparty).unwrap(), open_init);
+
+        let mut with_counterparty = event.attributes;
+        for attr in &mut with_counterparty {
+            if attr.key_str().unwrap() == COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY {
+                *attr = (COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY, "channel-5").into();
+            }
+        }
+        assert!(matches!(
+            OpenInit::try_from(with_counterparty),
+            Err(ChannelError::UnexpectedCounterpartyChannelId { counterparty_channel_id })
+                if counterparty_channel_id == "channel-5"
+        ));
+
+        // From `OpenTry` on, the counterparty channel identifier is required.
+        let open_try = OpenTry::new(
+            PortId::transfer(),
+            ChannelId::new(1),
+            PortId::transfer(),
+            ChannelId::zero(),
+            ConnectionId::zero(),
+            Version::new("ics20-1".to_string()),
+        );
+        let mut attributes = AbciEvent::from(open_try).attributes;
+        for attr in &mut attributes {
+            if attr.key_str().unwrap() == COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY {
+                *attr = (COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY, "").into();
+            }
+        }
+        assert!(matches!(
+            OpenTry::try_from(attributes),
+            Err(ChannelError::MissingAttributeKey { attribute_key })
+                if attribute_key == COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY
+        ));
+    }
+
+    #[test]
+    fn emit_lossy_packet_data() {
+        let mut packet = mock_packet(1);
+        packet.data = vec![b'a', 0xff, b'b'];
+        let send_packet = SendPacket::new(packet, Order::Unordered, ConnectionId::zero());
+
+        assert!(matches!(
+            AbciEvent::try_from(send_packet.clone()),
+            Err(ChannelError::NonUtf8PacketData)
+        ));
+
+        let event = send_packet.clone().into_lossy_event();
+        let value_of = |key: &str| {
+            event
+                .attributes
+                .iter()
+                .find(|attr| attr.key_str().unwrap() == key)
+                .map(|attr| attr.value_str().unwrap())
+        };
+        assert_eq!(value_of(PKT_DATA_ATTRIBUTE_KEY), Some("a\u{fffd}b"));
+        assert_eq!(value_of(PKT_DATA_HEX_ATTRIBUTE_KEY), Some("61ff62"));
+
+        // The exact data survives through `packet_data_hex`.
+        let parsed = SendPacket::try_from(event).unwrap();
+        assert_eq!(parsed.packet_data(), &[b'a', 0xff, b'b']);
+        assert!(parsed.eq_ignoring_deprecated(&send_packet));
+
+        // UTF-8 data is emitted identically in both modes.
+        let utf8 = SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero());
+        assert_eq!(
+            utf8.clone().into_lossy_event(),
+            AbciEvent::try_from(utf8).unwrap()
+        );
+    }
+
+    #[cfg(all(feature = "tracing", feature = "std"))]
+    #[test]
+    fn trace_packet_event_parsing() {
+        use core::fmt::Debug;
+        use std::sync::{Arc, Mutex};
+
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Level, Metadata, Subscriber};
+
+        /// Renders the fields of a span or an event as `name=value` pairs.
+        #[derive(Default)]
+        struct Fields(Vec<String>);
+
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+                self.0.push(format!("{}={value:?}", field.name()));
+            }
+        }
+
+        /// Captures the fields of every span and of every event.
+        #[derive(Clone, Default)]
+        struct Capture {
+            spans: Arc<Mutex<Vec<String>>>,
+            events: Arc<Mutex<Vec<(Level, String)>>>,
+        }
+
+        impl Subscriber for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut fields = Fields::default();
+                span.record(&mut fields);
+                let mut spans = self.spans.lock().unwrap();
+                spans.push(fields.0.join(" "));
+                Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, span: &Id, values: &Record<'_>) {
+                let mut fields = Fields::default();
+                values.record(&mut fields);
+                let mut spans = self.spans.lock().unwrap();
+                let index = span.into_u64() as usize - 1;
+                spans[index] = format!("{} {}", spans[index], fields.0.join(" "));
+            }
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields::default();
+                event.record(&mut fields);
+                self.events
+                    .lock()
+                    .unwrap()
+                    .push((*event.metadata().level(), fields.0.join(" ")));
+            }
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        let capture = Capture::default();
+        tracing::subscriber::with_default(capture.clone(), || {
+            SendPacket::try_from(send_packet_event(1)).unwrap();
+        });
+        assert_eq!(
+            capture.spans.lock().unwrap().as_slice(),
+            ["kind=send_packet channel=channel-0"]
+        );
+        assert!(capture.events.lock().unwrap().is_empty());
+
+        // Drop `packet_data_hex`, and repeat a non-standard attribute.
+        let mut anomalous = send_packet_event(1);
+        anomalous
+            .attributes
+            .retain(|attr| attr.key_str().unwrap() != PKT_DATA_HEX_ATTRIBUTE_KEY);
+        anomalous.attributes.extend([
+            ("packet_fee", "10uatom").into(),
+            ("packet_fee", "10uatom").into(),
+        ]);
+
+        let capture = Capture::default();
+        tracing::subscriber::with_default(capture.clone(), || {
+            SendPacket::try_from(anomalous).unwrap();
+        });
+        let events = capture.events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|(level, _)| *level == Level::WARN));
+        assert!(events[0].1.contains("key=\"packet_fee\""));
+        assert!(events[1].1.contains("key=\"packet_data\""));
+    }
 }
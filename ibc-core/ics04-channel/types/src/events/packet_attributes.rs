@@ -1,33 +1,60 @@
 //! This module holds all the abci event attributes for IBC events emitted
 //! during packet-related datagrams.
 //!
+//! Each attribute wraps a single value and converts from it with `From`, so
+//! that e.g. a `SrcChannelIdAttribute` is built with `channel_id.into()`.
+#[cfg(feature = "tracing")]
+use alloc::collections::BTreeSet;
 use core::str;
+use core::str::FromStr;
 
 use derive_more::From;
+use ibc_core_client_types::Height;
 use ibc_core_host_types::identifiers::{ChannelId, ConnectionId, PortId, Sequence};
 use ibc_primitives::prelude::*;
+use ibc_primitives::Expiry::Expired;
 use ibc_primitives::Timestamp;
-use subtle_encoding::hex;
 use tendermint::abci;
 
-use crate::acknowledgement::Acknowledgement;
+use super::attribute_map::AttributeMap;
+use super::{
+    attribute_key_matches, attribute_value, canonical_event_kind, invalid_attribute_value, keys,
+    missing_attribute_key, parse_attribute_value, record_parse_error, trimmed_attribute_value,
+    EmissionProfile, ModuleAttribute,
+};
+use crate::acknowledgement::{Acknowledgement, AcknowledgementShape};
 use crate::channel::Order;
-use crate::error::ChannelError;
+use crate::error::{AttributeValueError, ChannelError, HexDecodeError};
 use crate::timeout::TimeoutHeight;
 
-const PKT_SEQ_ATTRIBUTE_KEY: &str = "packet_sequence";
-const PKT_DATA_ATTRIBUTE_KEY: &str = "packet_data";
-const PKT_DATA_HEX_ATTRIBUTE_KEY: &str = "packet_data_hex";
-const PKT_SRC_PORT_ATTRIBUTE_KEY: &str = "packet_src_port";
-const PKT_SRC_CHANNEL_ATTRIBUTE_KEY: &str = "packet_src_channel";
-const PKT_DST_PORT_ATTRIBUTE_KEY: &str = "packet_dst_port";
-const PKT_DST_CHANNEL_ATTRIBUTE_KEY: &str = "packet_dst_channel";
-const PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY: &str = "packet_channel_ordering";
-const PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY: &str = "packet_timeout_height";
-const PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY: &str = "packet_timeout_timestamp";
-const PKT_ACK_ATTRIBUTE_KEY: &str = "packet_ack";
-const PKT_ACK_HEX_ATTRIBUTE_KEY: &str = "packet_ack_hex";
-const PKT_CONNECTION_ID_ATTRIBUTE_KEY: &str = "packet_connection";
+pub(super) const PKT_SEQ_ATTRIBUTE_KEY: &str = keys::PKT_SEQ;
+pub(super) const PKT_DATA_ATTRIBUTE_KEY: &str = keys::PKT_DATA;
+pub(super) const PKT_DATA_HEX_ATTRIBUTE_KEY: &str = keys::PKT_DATA_HEX;
+pub(super) const PKT_DATA_LEN_ATTRIBUTE_KEY: &str = keys::PKT_DATA_LEN;
+pub(super) const PKT_DATA_SHA256_ATTRIBUTE_KEY: &str = keys::PKT_DATA_SHA256;
+pub(super) const PKT_SRC_PORT_ATTRIBUTE_KEY: &str = keys::PKT_SRC_PORT;
+pub(super) const PKT_SRC_CHANNEL_ATTRIBUTE_KEY: &str = keys::PKT_SRC_CHANNEL;
+pub(super) const PKT_DST_PORT_ATTRIBUTE_KEY: &str = keys::PKT_DST_PORT;
+pub(super) const PKT_DST_CHANNEL_ATTRIBUTE_KEY: &str = keys::PKT_DST_CHANNEL;
+pub(super) const PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY: &str = keys::PKT_CHANNEL_ORDERING;
+pub(super) const PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY: &str = keys::PKT_TIMEOUT_HEIGHT;
+pub(super) const PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY: &str = keys::PKT_TIMEOUT_TIMESTAMP;
+pub(super) const PKT_ACK_ATTRIBUTE_KEY: &str = keys::PKT_ACK;
+pub(super) const PKT_ACK_HEX_ATTRIBUTE_KEY: &str = keys::PKT_ACK_HEX;
+pub(super) const PKT_CONNECTION_ID_ATTRIBUTE_KEY: &str = keys::PKT_CONNECTION_ID;
+#[cfg(feature = "base64-attributes")]
+pub(super) const PKT_DATA_B64_ATTRIBUTE_KEY: &str = keys::PKT_DATA_B64;
+#[cfg(feature = "legacy-attributes")]
+pub(super) const PKT_LEGACY_TIMEOUT_ATTRIBUTE_KEY: &str = keys::PKT_LEGACY_TIMEOUT;
+
+/// The letter case of hex-encoded attribute values. Defaults to lowercase,
+/// the casing emitted by ibc-go.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HexCase {
+    #[default]
+    Lower,
+    Upper,
+}
 
 #[cfg_attr(
     feature = "parity-scale-codec",
@@ -44,29 +71,194 @@ const PKT_CONNECTION_ID_ATTRIBUTE_KEY: &str = "packet_connection";
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct PacketDataAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "packetData"))]
     pub packet_data: Vec<u8>,
 }
 
-impl TryFrom<PacketDataAttribute> for Vec<abci::EventAttribute> {
-    type Error = ChannelError;
+impl PacketDataAttribute {
+    /// Decodes the value of the non-standard `packet_data_b64` attribute.
+    #[cfg(feature = "base64-attributes")]
+    fn try_from_base64(value: (&[u8], &[u8])) -> Result<Self, ChannelError> {
+        let value = trimmed_attribute_value(value, PKT_DATA_B64_ATTRIBUTE_KEY)?;
+        let packet_data = subtle_encoding::base64::decode(value).map_err(|_| {
+            record_parse_error(ChannelError::InvalidAttributeValue {
+                attribute_value: value.to_string(),
+                error: None,
+            })
+        })?;
 
-    fn try_from(attr: PacketDataAttribute) -> Result<Self, Self::Error> {
+        Ok(Self { packet_data })
+    }
+
+    /// Tells whether the packet data can be emitted as the deprecated
+    /// plaintext `packet_data` attribute, which requires valid UTF-8.
+    pub fn is_utf8(&self) -> bool {
+        str::from_utf8(&self.packet_data).is_ok()
+    }
+
+    /// Checks that this plaintext `packet_data` carries the same payload as
+    /// the decoded `packet_data_hex` attribute, reporting how they differ.
+    /// Also used to check the non-standard `packet_data_b64` attribute against
+    /// the other forms of the packet data.
+    pub fn check_consistent_with(&self, hex: &Self) -> Result<(), ChannelError> {
+        let (plaintext, hex) = (&self.packet_data, &hex.packet_data);
+        let offset = plaintext
+            .iter()
+            .zip(hex.iter())
+            .position(|(plaintext, hex)| plaintext != hex);
+
+        match offset {
+            Some(offset) => Err(ChannelError::PacketDataContentMismatch {
+                offset,
+                plaintext_len: plaintext.len(),
+                hex_len: hex.len(),
+            }),
+            None if plaintext.len() < hex.len() => Err(ChannelError::TruncatedPlaintextData {
+                plaintext_len: plaintext.len(),
+                hex_len: hex.len(),
+            }),
+            None if plaintext.len() > hex.len() => Err(ChannelError::OversizedPlaintextData {
+                plaintext_len: plaintext.len(),
+                hex_len: hex.len(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Tells whether this plaintext `packet_data` is the lossy UTF-8
+    /// rendering of the non-UTF-8 payload of `hex`, as emitted by
+    /// [`Self::into_lossy_attributes`].
+    fn is_lossy_rendering_of(&self, hex: &Self) -> bool {
+        !hex.is_utf8() && self.packet_data == String::from_utf8_lossy(&hex.packet_data).as_bytes()
+    }
+
+    /// Packet data is opaque, so any bytes are valid. Provided for parity
+    /// with the other attributes.
+    pub fn validate(&self) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    /// Emits the length and SHA-256 hash of the packet data in place of the
+    /// data itself, for logging events without persisting raw payloads.
+    pub fn redacted(&self) -> Vec<abci::EventAttribute> {
+        use sha2::Digest;
+
+        let hash = sha2::Sha256::digest(&self.packet_data);
+
+        vec![
+            (
+                PKT_DATA_LEN_ATTRIBUTE_KEY,
+                self.packet_data.len().to_string(),
+            )
+                .into(),
+            (
+                PKT_DATA_SHA256_ATTRIBUTE_KEY,
+                encode_hex(&hash, HexCase::Lower),
+            )
+                .into(),
+        ]
+    }
+
+    /// Emits the plaintext and hex-encoded attributes, with `packet_data_hex`
+    /// in the given letter case. Only meant for consumers requiring uppercase
+    /// hex, as events are emitted in lowercase by default.
+    pub fn into_attributes_with_hex_case(
+        self,
+        case: HexCase,
+    ) -> Result<Vec<abci::EventAttribute>, ChannelError> {
         let tags = vec![
             (
                 PKT_DATA_ATTRIBUTE_KEY,
-                str::from_utf8(&attr.packet_data).map_err(|_| ChannelError::NonUtf8PacketData)?,
+                str::from_utf8(&self.packet_data).map_err(|_| ChannelError::NonUtf8PacketData)?,
             )
                 .into(),
             (
                 PKT_DATA_HEX_ATTRIBUTE_KEY,
-                str::from_utf8(&hex::encode(attr.packet_data))
-                    .expect("Never fails because hexadecimal is valid UTF8"),
+                encode_hex(&self.packet_data, case),
             )
                 .into(),
         ];
 
         Ok(tags)
     }
+
+    /// Emits the plaintext and hex-encoded attributes like `TryFrom`, but
+    /// accepts packet data that is not valid UTF-8, for chains that must keep
+    /// emitting the deprecated `packet_data` key.
+    ///
+    /// This is lossy: invalid UTF-8 sequences are replaced with U+FFFD in the
+    /// plaintext attribute, which then no longer matches the payload. Only
+    /// `packet_data_hex` carries the exact data.
+    pub fn into_lossy_attributes(self) -> Vec<abci::EventAttribute> {
+        vec![
+            (
+                PKT_DATA_ATTRIBUTE_KEY,
+                String::from_utf8_lossy(&self.packet_data).into_owned(),
+            )
+                .into(),
+            self.hex_attribute(),
+        ]
+    }
+
+    /// Emits the attribute in the layout of the given profile. Packet data
+    /// only needs to be valid UTF-8 if the profile emits plaintext attributes.
+    pub fn into_attributes(
+        self,
+        profile: EmissionProfile,
+    ) -> Result<Vec<abci::EventAttribute>, ChannelError> {
+        if profile.emits_plaintext() {
+            return self.try_into();
+        }
+
+        Ok(vec![self.hex_attribute()])
+    }
+
+    /// The lowercase `packet_data_hex` attribute alone.
+    pub(super) fn hex_attribute(&self) -> abci::EventAttribute {
+        (
+            PKT_DATA_HEX_ATTRIBUTE_KEY,
+            encode_hex(&self.packet_data, HexCase::Lower),
+        )
+            .into()
+    }
+}
+
+/// Emits `packet_data_hex` in lowercase, as ibc-go does.
+impl TryFrom<PacketDataAttribute> for Vec<abci::EventAttribute> {
+    type Error = ChannelError;
+
+    fn try_from(attr: PacketDataAttribute) -> Result<Self, Self::Error> {
+        attr.into_attributes_with_hex_case(HexCase::Lower)
+    }
+}
+
+/// Parses either the hex-encoded `packet_data_hex` attribute, or the
+/// deprecated plaintext `packet_data` one.
+impl TryFrom<(&[u8], &[u8])> for PacketDataAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        if !is_hex_attribute(value, PKT_DATA_HEX_ATTRIBUTE_KEY) {
+            return attribute_value(value, PKT_DATA_ATTRIBUTE_KEY).map(|value| {
+                PacketDataAttribute {
+                    packet_data: value.as_bytes().to_vec(),
+                }
+            });
+        }
+
+        let hex_value = attribute_value(value, PKT_DATA_HEX_ATTRIBUTE_KEY)?;
+        let packet_data = decode_hex_attribute_value(hex_value)?;
+
+        Ok(PacketDataAttribute { packet_data })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for PacketDataAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
 }
 
 #[cfg_attr(
@@ -84,9 +276,68 @@ impl TryFrom<PacketDataAttribute> for Vec<abci::EventAttribute> {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct TimeoutHeightAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "timeoutHeight"))]
     pub timeout_height: TimeoutHeight,
 }
 
+impl TimeoutHeightAttribute {
+    /// Builds the attribute of a packet that never times out by height.
+    pub const fn never() -> Self {
+        Self {
+            timeout_height: TimeoutHeight::Never,
+        }
+    }
+
+    /// Moves a timeout height from revision `from` to revision `to`, keeping
+    /// its revision height. Other timeouts are left untouched.
+    pub fn remap_revision(&mut self, from: u64, to: u64) {
+        if let TimeoutHeight::At(height) = self.timeout_height {
+            if height.revision_number() == from {
+                if let Ok(remapped) = Height::new(to, height.revision_height()) {
+                    self.timeout_height = TimeoutHeight::At(remapped);
+                }
+            }
+        }
+    }
+
+    /// Compares two timeout heights, treating an explicit zero height as no
+    /// timeout.
+    ///
+    /// Some event sources decode the `0-0` height into a zero [`Height`]
+    /// rather than [`TimeoutHeight::Never`], although both mean the packet
+    /// never times out by height. The derived `PartialEq` remains strict and
+    /// tells the two representations apart.
+    pub fn semantic_eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+
+    fn normalized(&self) -> TimeoutHeight {
+        match self.timeout_height {
+            TimeoutHeight::At(height)
+                if height.revision_number() == 0 && height.revision_height() == 0 =>
+            {
+                TimeoutHeight::Never
+            }
+            timeout_height => timeout_height,
+        }
+    }
+}
+
+/// Defaults to a packet that never times out by height.
+impl Default for TimeoutHeightAttribute {
+    fn default() -> Self {
+        Self::never()
+    }
+}
+
+impl From<Height> for TimeoutHeightAttribute {
+    fn from(height: Height) -> Self {
+        Self {
+            timeout_height: TimeoutHeight::At(height),
+        }
+    }
+}
+
 impl From<TimeoutHeightAttribute> for abci::EventAttribute {
     fn from(attr: TimeoutHeightAttribute) -> Self {
         match attr.timeout_height {
@@ -98,6 +349,37 @@ impl From<TimeoutHeightAttribute> for abci::EventAttribute {
     }
 }
 
+/// Parses a `{revision_number}-{revision_height}` timeout height, where `0-0`
+/// stands for no timeout height.
+///
+/// Revision number zero is valid, e.g. `0-100` on chains that never upgraded,
+/// and is parsed as is. Only a zero revision height is rejected.
+impl TryFrom<(&[u8], &[u8])> for TimeoutHeightAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        let value = trimmed_attribute_value(value, PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY)?;
+
+        let timeout_height = if value == "0-0" {
+            TimeoutHeight::Never
+        } else {
+            Height::from_str(value)
+                .map(TimeoutHeight::At)
+                .map_err(|e| invalid_attribute_value(value, e))?
+        };
+
+        Ok(TimeoutHeightAttribute { timeout_height })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for TimeoutHeightAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -113,9 +395,36 @@ impl From<TimeoutHeightAttribute> for abci::EventAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct TimeoutTimestampAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "timeoutTimestamp"))]
     pub timeout_timestamp: Timestamp,
 }
 
+/// Defaults to a packet that never times out by timestamp.
+impl Default for TimeoutTimestampAttribute {
+    fn default() -> Self {
+        Self {
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+}
+
+impl TimeoutTimestampAttribute {
+    /// Emits the timeout timestamp in whole seconds instead of nanoseconds,
+    /// for legacy consumers expecting that unit.
+    ///
+    /// This is lossy, as sub-second precision is truncated, and the result
+    /// cannot be parsed back by this crate. Events are emitted in nanoseconds
+    /// through the `From` conversion unless a consumer specifically requires
+    /// seconds.
+    pub fn into_attribute_seconds(self) -> abci::EventAttribute {
+        (
+            PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+            (self.timeout_timestamp.nanoseconds() / 1_000_000_000).to_string(),
+        )
+            .into()
+    }
+}
+
 impl From<TimeoutTimestampAttribute> for abci::EventAttribute {
     fn from(attr: TimeoutTimestampAttribute) -> Self {
         (
@@ -126,6 +435,129 @@ impl From<TimeoutTimestampAttribute> for abci::EventAttribute {
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for TimeoutTimestampAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        let value = trimmed_attribute_value(value, PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY)?;
+
+        let nanoseconds = value
+            .parse::<u64>()
+            .map_err(|e| invalid_attribute_value(value, e))?;
+        let timeout_timestamp = Timestamp::from_nanoseconds(nanoseconds)
+            .map_err(|e| invalid_attribute_value(value, e))?;
+
+        Ok(TimeoutTimestampAttribute { timeout_timestamp })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for TimeoutTimestampAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
+/// The timeout of a packet, reached as soon as either its timeout height or
+/// its timeout timestamp is reached on the destination chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PacketTimeout {
+    pub timeout_height: TimeoutHeightAttribute,
+    pub timeout_timestamp: TimeoutTimestampAttribute,
+}
+
+impl PacketTimeout {
+    pub fn new(timeout_height: TimeoutHeight, timeout_timestamp: Timestamp) -> Self {
+        Self {
+            timeout_height: timeout_height.into(),
+            timeout_timestamp: timeout_timestamp.into(),
+        }
+    }
+
+    /// Tells whether the timeout has elapsed on the destination chain at the
+    /// given height and time, with the same semantics as
+    /// [`Packet::timed_out`](crate::packet::Packet::timed_out). Unset
+    /// timeouts never elapse.
+    pub fn is_elapsed(&self, at_height: Height, at_time: Timestamp) -> bool {
+        let height_elapsed = self.timeout_height.timeout_height.has_expired(at_height);
+
+        let timeout_timestamp = &self.timeout_timestamp.timeout_timestamp;
+        let timestamp_elapsed =
+            timeout_timestamp.is_set() && at_time.check_expiry(timeout_timestamp) == Expired;
+
+        height_elapsed || timestamp_elapsed
+    }
+}
+
+/// The singular `packet_timeout` attribute emitted by some very old chains,
+/// combining the timeout height and timestamp as
+/// `<revision_number>-<revision_height>,<timestamp_nanoseconds>`.
+///
+/// No released version of ibc-go, nor of the `x/ibc` module of the Cosmos
+/// SDK it originates from, emits it: they have always emitted the separate
+/// `packet_timeout_height` and `packet_timeout_timestamp` attributes. The
+/// format is the one reported for the pre-release chains indexers ran into,
+/// and is not backed by a specification.
+///
+/// The packet event parsers recognize it when the separate attributes are
+/// absent, see [`PacketEventAttributes`].
+#[cfg(feature = "legacy-attributes")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LegacyPacketTimeoutAttribute {
+    pub timeout_height: TimeoutHeight,
+    pub timeout_timestamp: Timestamp,
+}
+
+#[cfg(feature = "legacy-attributes")]
+impl LegacyPacketTimeoutAttribute {
+    /// Splits the combined timeout into the attributes emitted nowadays.
+    pub fn split(self) -> (TimeoutHeightAttribute, TimeoutTimestampAttribute) {
+        (self.timeout_height.into(), self.timeout_timestamp.into())
+    }
+}
+
+#[cfg(feature = "legacy-attributes")]
+impl TryFrom<(&[u8], &[u8])> for LegacyPacketTimeoutAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        let value = trimmed_attribute_value(value, PKT_LEGACY_TIMEOUT_ATTRIBUTE_KEY)?;
+
+        let (height, timestamp) = value.split_once(',').ok_or_else(|| {
+            record_parse_error(ChannelError::InvalidAttributeValue {
+                attribute_value: value.to_string(),
+                error: None,
+            })
+        })?;
+
+        let timeout_height = TimeoutHeightAttribute::try_from((
+            PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY.as_bytes(),
+            height.as_bytes(),
+        ))?
+        .timeout_height;
+        let timeout_timestamp = TimeoutTimestampAttribute::try_from((
+            PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY.as_bytes(),
+            timestamp.as_bytes(),
+        ))?
+        .timeout_timestamp;
+
+        Ok(Self {
+            timeout_height,
+            timeout_timestamp,
+        })
+    }
+}
+
+#[cfg(feature = "legacy-attributes")]
+impl TryFrom<abci::EventAttribute> for LegacyPacketTimeoutAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -144,12 +576,39 @@ pub struct SequenceAttribute {
     pub sequence: Sequence,
 }
 
+/// Defaults to sequence 1, the first sequence of a channel.
+impl Default for SequenceAttribute {
+    fn default() -> Self {
+        Self {
+            sequence: Sequence::from(1),
+        }
+    }
+}
+
 impl From<SequenceAttribute> for abci::EventAttribute {
     fn from(attr: SequenceAttribute) -> Self {
         (PKT_SEQ_ATTRIBUTE_KEY, attr.sequence.to_string()).into()
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for SequenceAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(SequenceAttribute {
+            sequence: parse_attribute_value(value, PKT_SEQ_ATTRIBUTE_KEY)?,
+        })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for SequenceAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -165,15 +624,40 @@ impl From<SequenceAttribute> for abci::EventAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct SrcPortIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "srcPortId"))]
     pub src_port_id: PortId,
 }
 
-impl From<SrcPortIdAttribute> for abci::EventAttribute {
-    fn from(attr: SrcPortIdAttribute) -> Self {
+impl From<&SrcPortIdAttribute> for abci::EventAttribute {
+    fn from(attr: &SrcPortIdAttribute) -> Self {
         (PKT_SRC_PORT_ATTRIBUTE_KEY, attr.src_port_id.as_str()).into()
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for SrcPortIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(SrcPortIdAttribute {
+            src_port_id: parse_attribute_value(value, PKT_SRC_PORT_ATTRIBUTE_KEY)?,
+        })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for SrcPortIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
+impl AsRef<str> for SrcPortIdAttribute {
+    fn as_ref(&self) -> &str {
+        self.src_port_id.as_str()
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -189,15 +673,58 @@ impl From<SrcPortIdAttribute> for abci::EventAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct SrcChannelIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "srcChannelId"))]
     pub src_channel_id: ChannelId,
 }
 
-impl From<SrcChannelIdAttribute> for abci::EventAttribute {
-    fn from(attr: SrcChannelIdAttribute) -> Self {
+impl From<&SrcChannelIdAttribute> for abci::EventAttribute {
+    fn from(attr: &SrcChannelIdAttribute) -> Self {
         (PKT_SRC_CHANNEL_ATTRIBUTE_KEY, attr.src_channel_id.as_str()).into()
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for SrcChannelIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(SrcChannelIdAttribute {
+            src_channel_id: parse_attribute_value(value, PKT_SRC_CHANNEL_ATTRIBUTE_KEY)?,
+        })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for SrcChannelIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
+impl AsRef<str> for SrcChannelIdAttribute {
+    fn as_ref(&self) -> &str {
+        self.src_channel_id.as_str()
+    }
+}
+
+impl SrcChannelIdAttribute {
+    /// Parses the attribute like `TryFrom`, requiring the channel identifier
+    /// to be in the canonical `channel-{N}` form. `TryFrom` already rejects
+    /// identifiers that are not, e.g. `channel-05`, so this only spells out
+    /// the requirement at call sites that depend on it.
+    pub fn try_from_strict(value: abci::EventAttribute) -> Result<Self, ChannelError> {
+        let attr = Self::try_from(value)?;
+        validate_canonical_channel_id(&attr.src_channel_id)?;
+        Ok(attr)
+    }
+
+    /// The counter of the source channel identifier, i.e. `N` in
+    /// `channel-N`, or `None` if the identifier is not in this canonical form.
+    pub fn numeric_suffix(&self) -> Option<u64> {
+        self.src_channel_id.sequence()
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -213,15 +740,40 @@ impl From<SrcChannelIdAttribute> for abci::EventAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct DstPortIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "dstPortId"))]
     pub dst_port_id: PortId,
 }
 
-impl From<DstPortIdAttribute> for abci::EventAttribute {
-    fn from(attr: DstPortIdAttribute) -> Self {
+impl From<&DstPortIdAttribute> for abci::EventAttribute {
+    fn from(attr: &DstPortIdAttribute) -> Self {
         (PKT_DST_PORT_ATTRIBUTE_KEY, attr.dst_port_id.as_str()).into()
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for DstPortIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(DstPortIdAttribute {
+            dst_port_id: parse_attribute_value(value, PKT_DST_PORT_ATTRIBUTE_KEY)?,
+        })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for DstPortIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
+impl AsRef<str> for DstPortIdAttribute {
+    fn as_ref(&self) -> &str {
+        self.dst_port_id.as_str()
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -237,15 +789,58 @@ impl From<DstPortIdAttribute> for abci::EventAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct DstChannelIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "dstChannelId"))]
     pub dst_channel_id: ChannelId,
 }
 
-impl From<DstChannelIdAttribute> for abci::EventAttribute {
-    fn from(attr: DstChannelIdAttribute) -> Self {
+impl From<&DstChannelIdAttribute> for abci::EventAttribute {
+    fn from(attr: &DstChannelIdAttribute) -> Self {
         (PKT_DST_CHANNEL_ATTRIBUTE_KEY, attr.dst_channel_id.as_str()).into()
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for DstChannelIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(DstChannelIdAttribute {
+            dst_channel_id: parse_attribute_value(value, PKT_DST_CHANNEL_ATTRIBUTE_KEY)?,
+        })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for DstChannelIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
+impl AsRef<str> for DstChannelIdAttribute {
+    fn as_ref(&self) -> &str {
+        self.dst_channel_id.as_str()
+    }
+}
+
+impl DstChannelIdAttribute {
+    /// Parses the attribute like `TryFrom`, requiring the channel identifier
+    /// to be in the canonical `channel-{N}` form. `TryFrom` already rejects
+    /// identifiers that are not, e.g. `channel-05`, so this only spells out
+    /// the requirement at call sites that depend on it.
+    pub fn try_from_strict(value: abci::EventAttribute) -> Result<Self, ChannelError> {
+        let attr = Self::try_from(value)?;
+        validate_canonical_channel_id(&attr.dst_channel_id)?;
+        Ok(attr)
+    }
+
+    /// The counter of the destination channel identifier, i.e. `N` in
+    /// `channel-N`, or `None` if the identifier is not in this canonical form.
+    pub fn numeric_suffix(&self) -> Option<u64> {
+        self.dst_channel_id.sequence()
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -264,12 +859,63 @@ pub struct ChannelOrderingAttribute {
     pub order: Order,
 }
 
+impl ChannelOrderingAttribute {
+    /// Checks the reported ordering against the one of the known channel end
+    /// the event relates to.
+    pub fn verify_ordering(&self, expected: Order) -> Result<(), ChannelError> {
+        if self.order != expected {
+            return Err(ChannelError::ChannelEndOrderingMismatch {
+                expected,
+                actual: self.order,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Emits the ordering as its numeric protobuf value (e.g. `"2"`) rather
+    /// than the `ORDER_ORDERED`-style string, for chains storing it that way.
+    pub fn into_proto_attribute(self) -> abci::EventAttribute {
+        (
+            PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+            self.order.as_proto_i32().to_string(),
+        )
+            .into()
+    }
+}
+
 impl From<ChannelOrderingAttribute> for abci::EventAttribute {
     fn from(attr: ChannelOrderingAttribute) -> Self {
         (PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY, attr.order.as_str()).into()
     }
 }
 
+/// Accepts both the string form of the ordering (e.g. `ORDER_ORDERED`) and
+/// its numeric protobuf value (e.g. `2`).
+impl TryFrom<(&[u8], &[u8])> for ChannelOrderingAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        let value = trimmed_attribute_value(value, PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY)?;
+
+        let order = match value.parse::<i32>() {
+            Ok(proto_order) => Order::from_i32(proto_order),
+            Err(_) => Order::from_str(value),
+        }
+        .map_err(|e| invalid_attribute_value(value, AttributeValueError::Ordering(Box::new(e))))?;
+
+        Ok(ChannelOrderingAttribute { order })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for ChannelOrderingAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -285,15 +931,34 @@ impl From<ChannelOrderingAttribute> for abci::EventAttribute {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, From, PartialEq, Eq)]
 pub struct PacketConnectionIdAttribute {
+    #[cfg_attr(feature = "serde", serde(alias = "connectionId"))]
     pub connection_id: ConnectionId,
 }
 
-impl From<PacketConnectionIdAttribute> for abci::EventAttribute {
-    fn from(attr: PacketConnectionIdAttribute) -> Self {
+impl From<&PacketConnectionIdAttribute> for abci::EventAttribute {
+    fn from(attr: &PacketConnectionIdAttribute) -> Self {
         (PKT_CONNECTION_ID_ATTRIBUTE_KEY, attr.connection_id.as_str()).into()
     }
 }
 
+impl TryFrom<(&[u8], &[u8])> for PacketConnectionIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        Ok(PacketConnectionIdAttribute {
+            connection_id: parse_attribute_value(value, PKT_CONNECTION_ID_ATTRIBUTE_KEY)?,
+        })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for PacketConnectionIdAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -316,25 +981,743 @@ impl TryFrom<AcknowledgementAttribute> for Vec<abci::EventAttribute> {
     type Error = ChannelError;
 
     fn try_from(attr: AcknowledgementAttribute) -> Result<Self, Self::Error> {
-        let tags = vec![
-            (
-                PKT_ACK_ATTRIBUTE_KEY,
-                // Note: this attribute forces us to assume that Packet data
-                // is valid UTF-8, even though the standard doesn't require
-                // it. It has been deprecated in ibc-go. It will be removed
-                // in the future.
-                str::from_utf8(attr.acknowledgement.as_bytes())
-                    .map_err(|_| ChannelError::NonUtf8PacketData)?,
-            )
-                .into(),
+        // ICS-04 forbids empty acknowledgements. `Acknowledgement::try_from`
+        // already enforces this, but decoding codecs bypass that check.
+        if attr.acknowledgement.as_bytes().is_empty() {
+            return Err(ChannelError::EmptyAcknowledgement);
+        }
+
+        let mut tags = Vec::with_capacity(2);
+
+        // Note: this attribute forces us to assume that Packet data is valid
+        // UTF-8, even though the standard doesn't require it. It has been
+        // deprecated in ibc-go. It will be removed in the future. With the
+        // `skip-deprecated-ack` feature, it is left out for binary
+        // acknowledgements, which are then only emitted as `packet_ack_hex`.
+        match str::from_utf8(attr.acknowledgement.as_bytes()) {
+            Ok(acknowledgement) => tags.push((PKT_ACK_ATTRIBUTE_KEY, acknowledgement).into()),
+            #[cfg(feature = "skip-deprecated-ack")]
+            Err(_) => {}
+            #[cfg(not(feature = "skip-deprecated-ack"))]
+            Err(_) => return Err(ChannelError::NonUtf8PacketData),
+        }
+
+        tags.push(
             (
                 PKT_ACK_HEX_ATTRIBUTE_KEY,
-                str::from_utf8(&hex::encode(attr.acknowledgement))
-                    .expect("Never fails because hexadecimal is always valid UTF-8"),
+                encode_hex(attr.acknowledgement.as_bytes(), HexCase::Lower),
             )
                 .into(),
-        ];
+        );
 
         Ok(tags)
     }
 }
+
+/// Parses either the hex-encoded `packet_ack_hex` attribute, or the
+/// deprecated plaintext `packet_ack` one.
+impl TryFrom<(&[u8], &[u8])> for AcknowledgementAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: (&[u8], &[u8])) -> Result<Self, Self::Error> {
+        let (raw_value, bytes) = if is_hex_attribute(value, PKT_ACK_HEX_ATTRIBUTE_KEY) {
+            let hex_value = attribute_value(value, PKT_ACK_HEX_ATTRIBUTE_KEY)?;
+            let bytes = decode_hex_attribute_value(hex_value)?;
+            (hex_value, bytes)
+        } else {
+            let value = attribute_value(value, PKT_ACK_ATTRIBUTE_KEY)?;
+            (value, value.as_bytes().to_vec())
+        };
+
+        let acknowledgement = Acknowledgement::try_from(bytes).map_err(|e| {
+            invalid_attribute_value(raw_value, AttributeValueError::Acknowledgement(Box::new(e)))
+        })?;
+
+        Ok(AcknowledgementAttribute { acknowledgement })
+    }
+}
+
+impl TryFrom<abci::EventAttribute> for AcknowledgementAttribute {
+    type Error = ChannelError;
+
+    fn try_from(value: abci::EventAttribute) -> Result<Self, Self::Error> {
+        Self::try_from((value.key_bytes(), value.value_bytes()))
+    }
+}
+
+/// Checks that the channel identifier is exactly what `ChannelId::new` would
+/// produce for some counter, e.g. `channel-5`.
+fn validate_canonical_channel_id(channel_id: &ChannelId) -> Result<(), ChannelError> {
+    if channel_id.sequence().is_none() {
+        return Err(record_parse_error(ChannelError::NonCanonicalChannelId {
+            channel_id: channel_id.to_string(),
+        }));
+    }
+
+    Ok(())
+}
+
+impl_borrowed_emission!(
+    SrcPortIdAttribute,
+    SrcChannelIdAttribute,
+    DstPortIdAttribute,
+    DstChannelIdAttribute,
+    PacketConnectionIdAttribute,
+);
+
+impl_validate_by_reparsing!(
+    TimeoutHeightAttribute,
+    TimeoutTimestampAttribute,
+    SequenceAttribute,
+    SrcPortIdAttribute,
+    SrcChannelIdAttribute,
+    DstPortIdAttribute,
+    DstChannelIdAttribute,
+    ChannelOrderingAttribute,
+    PacketConnectionIdAttribute,
+);
+
+impl_attribute_key!(
+    TimeoutHeightAttribute => PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY,
+    TimeoutTimestampAttribute => PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+    SequenceAttribute => PKT_SEQ_ATTRIBUTE_KEY,
+    SrcPortIdAttribute => PKT_SRC_PORT_ATTRIBUTE_KEY,
+    SrcChannelIdAttribute => PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+    DstPortIdAttribute => PKT_DST_PORT_ATTRIBUTE_KEY,
+    DstChannelIdAttribute => PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+    ChannelOrderingAttribute => PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+    PacketConnectionIdAttribute => PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+);
+
+impl AcknowledgementAttribute {
+    /// The lowercase `packet_ack_hex` attribute alone.
+    pub(super) fn hex_attribute(&self) -> abci::EventAttribute {
+        (
+            PKT_ACK_HEX_ATTRIBUTE_KEY,
+            encode_hex(self.acknowledgement.as_bytes(), HexCase::Lower),
+        )
+            .into()
+    }
+
+    /// Builds the attribute of the given acknowledgement bytes, which must
+    /// not be empty.
+    pub fn new(acknowledgement: Vec<u8>) -> Result<Self, ChannelError> {
+        Acknowledgement::new(acknowledgement).map(Self::from)
+    }
+
+    /// Classifies the acknowledgement as a success or an error, see
+    /// [`Acknowledgement::shape`].
+    pub fn shape(&self) -> AcknowledgementShape {
+        self.acknowledgement.shape()
+    }
+
+    /// Re-checks that the acknowledgement is not empty, e.g. after decoding it
+    /// with a non-validating codec.
+    pub fn validate(&self) -> Result<(), ChannelError> {
+        if self.acknowledgement.as_bytes().is_empty() {
+            return Err(ChannelError::EmptyAcknowledgement);
+        }
+
+        Ok(())
+    }
+
+    /// Emits the attribute in the layout of the given profile. The
+    /// acknowledgement only needs to be valid UTF-8 if the profile emits
+    /// plaintext attributes and the `skip-deprecated-ack` feature is off.
+    pub fn into_attributes(
+        self,
+        profile: EmissionProfile,
+    ) -> Result<Vec<abci::EventAttribute>, ChannelError> {
+        if profile.emits_plaintext() {
+            return self.try_into();
+        }
+
+        self.validate()?;
+
+        Ok(vec![self.hex_attribute()])
+    }
+}
+
+/// Decodes a hex attribute value, reporting where and why decoding failed.
+fn decode_hex_attribute_value(value: &str) -> Result<Vec<u8>, ChannelError> {
+    decode_hex(value).map_err(|error| {
+        record_parse_error(ChannelError::InvalidHexAttributeValue {
+            attribute_value: value.to_string(),
+            error,
+        })
+    })
+}
+
+/// Encodes bytes as hex of the given letter case.
+fn encode_hex(bytes: &[u8], case: HexCase) -> String {
+    let digits: &[u8; 16] = match case {
+        HexCase::Lower => b"0123456789abcdef",
+        HexCase::Upper => b"0123456789ABCDEF",
+    };
+
+    let mut encoded = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        encoded.push(char::from(digits[usize::from(byte >> 4)]));
+        encoded.push(char::from(digits[usize::from(byte & 0x0f)]));
+    }
+    encoded
+}
+
+/// Decodes a hex string of either casing.
+fn decode_hex(value: &str) -> Result<Vec<u8>, HexDecodeError> {
+    let nibbles = value
+        .char_indices()
+        .map(|(position, character)| {
+            character.to_digit(16).map(|nibble| nibble as u8).ok_or(
+                HexDecodeError::InvalidCharacter {
+                    character,
+                    position,
+                },
+            )
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    if nibbles.len() % 2 != 0 {
+        return Err(HexDecodeError::OddLength {
+            length: nibbles.len(),
+        });
+    }
+
+    Ok(nibbles
+        .chunks_exact(2)
+        .map(|pair| (pair[0] << 4) | pair[1])
+        .collect())
+}
+
+/// The keys of the standard attributes of packet events.
+const PACKET_EVENT_ATTRIBUTE_KEYS: &[&str] = &[
+    PKT_DATA_HEX_ATTRIBUTE_KEY,
+    PKT_DATA_ATTRIBUTE_KEY,
+    #[cfg(feature = "base64-attributes")]
+    PKT_DATA_B64_ATTRIBUTE_KEY,
+    #[cfg(feature = "legacy-attributes")]
+    PKT_LEGACY_TIMEOUT_ATTRIBUTE_KEY,
+    PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY,
+    PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+    PKT_SEQ_ATTRIBUTE_KEY,
+    PKT_SRC_PORT_ATTRIBUTE_KEY,
+    PKT_SRC_CHANNEL_ATTRIBUTE_KEY,
+    PKT_DST_PORT_ATTRIBUTE_KEY,
+    PKT_DST_CHANNEL_ATTRIBUTE_KEY,
+    PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+    PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+    PKT_ACK_HEX_ATTRIBUTE_KEY,
+    PKT_ACK_ATTRIBUTE_KEY,
+    keys::MODULE,
+];
+
+/// Resolves the key of a packet event attribute to the standard key it
+/// matches, if any, so that the `lenient-attributes` feature applies.
+pub(super) fn packet_event_attribute_key(key: &str) -> Option<&'static str> {
+    PACKET_EVENT_ATTRIBUTE_KEYS
+        .iter()
+        .copied()
+        .find(|expected| attribute_key_matches(key, expected))
+}
+
+/// Tells whether the attribute is the hex-encoded variant of a dual attribute.
+fn is_hex_attribute((key, _): (&[u8], &[u8]), hex_key: &str) -> bool {
+    str::from_utf8(key).is_ok_and(|key| attribute_key_matches(key, hex_key))
+}
+
+/// The non-standard attributes of a packet event, e.g. attached by a fee
+/// middleware, in the order in which they were emitted.
+///
+/// The binary codecs encode each attribute as its key and value bytes along
+/// with its index flag, and whether it is a pre-v0.37 attribute, so that
+/// decoding yields the very same attributes.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[derive(Clone, Debug, Default, From, PartialEq, Eq)]
+pub struct ExtraAttributes {
+    pub attributes: Vec<abci::EventAttribute>,
+}
+
+/// An extra attribute as encoded by the binary codecs: whether it is a
+/// v0.34 attribute, its key, its value and its index flag.
+#[cfg(any(feature = "borsh", feature = "parity-scale-codec"))]
+type RawExtraAttribute = (bool, Vec<u8>, Vec<u8>, bool);
+
+#[cfg(any(feature = "borsh", feature = "parity-scale-codec"))]
+impl ExtraAttributes {
+    fn to_raw(&self) -> Vec<RawExtraAttribute> {
+        self.attributes
+            .iter()
+            .map(|attr| {
+                (
+                    matches!(attr, abci::EventAttribute::V034(_)),
+                    attr.key_bytes().to_vec(),
+                    attr.value_bytes().to_vec(),
+                    attr.index(),
+                )
+            })
+            .collect()
+    }
+
+    fn from_raw(raw: Vec<RawExtraAttribute>) -> Result<Self, alloc::string::FromUtf8Error> {
+        let attributes = raw
+            .into_iter()
+            .map(|(v0_34, key, value, index)| {
+                if v0_34 {
+                    Ok(abci::EventAttribute::V034(abci::v0_34::EventAttribute {
+                        key,
+                        value,
+                        index,
+                    }))
+                } else {
+                    Ok((String::from_utf8(key)?, String::from_utf8(value)?, index).into())
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { attributes })
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for ExtraAttributes {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        borsh::BorshSerialize::serialize(&self.to_raw(), writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for ExtraAttributes {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let raw = Vec::<RawExtraAttribute>::deserialize_reader(reader)?;
+        Ok(Self::from_raw(raw).map_err(|_| borsh::io::ErrorKind::InvalidData)?)
+    }
+}
+
+#[cfg(feature = "parity-scale-codec")]
+impl parity_scale_codec::Encode for ExtraAttributes {
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, writer: &mut T) {
+        self.to_raw().encode_to(writer);
+    }
+}
+
+#[cfg(feature = "parity-scale-codec")]
+impl parity_scale_codec::Decode for ExtraAttributes {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> Result<Self, parity_scale_codec::Error> {
+        let raw = Vec::<RawExtraAttribute>::decode(input)?;
+        Self::from_raw(raw)
+            .map_err(|_| parity_scale_codec::Error::from("attribute is not valid UTF-8"))
+    }
+}
+
+#[cfg(feature = "parity-scale-codec")]
+impl scale_info::TypeInfo for ExtraAttributes {
+    type Identity = Self;
+
+    fn type_info() -> scale_info::Type {
+        scale_info::Type::builder()
+            .path(scale_info::Path::new("ExtraAttributes", module_path!()))
+            .composite(scale_info::build::Fields::named().field(|f| {
+                f.ty::<Vec<RawExtraAttribute>>()
+                    .name("attributes")
+                    .type_name("Vec<(bool, Vec<u8>, Vec<u8>, bool)>")
+            }))
+    }
+}
+
+/// Any one attribute of a packet event, for building events out of a
+/// heterogeneous collection of attributes, see [`PacketAttributes`].
+#[derive(Clone, Debug, From, PartialEq, Eq)]
+pub enum PacketAttribute {
+    PacketData(PacketDataAttribute),
+    TimeoutHeight(TimeoutHeightAttribute),
+    TimeoutTimestamp(TimeoutTimestampAttribute),
+    Sequence(SequenceAttribute),
+    SrcPortId(SrcPortIdAttribute),
+    SrcChannelId(SrcChannelIdAttribute),
+    DstPortId(DstPortIdAttribute),
+    DstChannelId(DstChannelIdAttribute),
+    Acknowledgement(AcknowledgementAttribute),
+    ChannelOrdering(ChannelOrderingAttribute),
+    ConnectionId(PacketConnectionIdAttribute),
+}
+
+impl PacketAttribute {
+    /// The position of the attribute in emitted events.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::PacketData(_) => 0,
+            Self::TimeoutHeight(_) => 1,
+            Self::TimeoutTimestamp(_) => 2,
+            Self::Sequence(_) => 3,
+            Self::SrcPortId(_) => 4,
+            Self::SrcChannelId(_) => 5,
+            Self::DstPortId(_) => 6,
+            Self::DstChannelId(_) => 7,
+            Self::Acknowledgement(_) => 8,
+            Self::ChannelOrdering(_) => 9,
+            Self::ConnectionId(_) => 10,
+        }
+    }
+
+    fn emit(self, attributes: &mut Vec<abci::EventAttribute>) -> Result<(), ChannelError> {
+        match self {
+            Self::PacketData(attr) => attributes.extend(Vec::try_from(attr)?),
+            Self::Acknowledgement(attr) => attributes.extend(Vec::try_from(attr)?),
+            Self::TimeoutHeight(attr) => attributes.push(attr.into()),
+            Self::TimeoutTimestamp(attr) => attributes.push(attr.into()),
+            Self::Sequence(attr) => attributes.push(attr.into()),
+            Self::SrcPortId(attr) => attributes.push(attr.into()),
+            Self::SrcChannelId(attr) => attributes.push(attr.into()),
+            Self::DstPortId(attr) => attributes.push(attr.into()),
+            Self::DstChannelId(attr) => attributes.push(attr.into()),
+            Self::ChannelOrdering(attr) => attributes.push(attr.into()),
+            Self::ConnectionId(attr) => attributes.push(attr.into()),
+        }
+
+        Ok(())
+    }
+}
+
+/// The attributes of a packet event, collected from [`PacketAttribute`]s in
+/// any order and emitted in the order used by ibc-go.
+///
+/// Each attribute is emitted like its own `TryFrom` conversion, so emission
+/// fails in the same cases, e.g. for packet data that is not valid UTF-8.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PacketAttributes(Vec<PacketAttribute>);
+
+impl FromIterator<PacketAttribute> for PacketAttributes {
+    fn from_iter<I: IntoIterator<Item = PacketAttribute>>(iter: I) -> Self {
+        let mut attributes: Vec<PacketAttribute> = iter.into_iter().collect();
+        attributes.sort_by_key(PacketAttribute::rank);
+        Self(attributes)
+    }
+}
+
+impl TryFrom<PacketAttributes> for Vec<abci::EventAttribute> {
+    type Error = ChannelError;
+
+    fn try_from(packet_attributes: PacketAttributes) -> Result<Self, Self::Error> {
+        let mut attributes = Vec::with_capacity(packet_attributes.0.len() + 2);
+        for attribute in packet_attributes.0 {
+            attribute.emit(&mut attributes)?;
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// Collects the attributes of a packet event while parsing an `abci::Event`.
+///
+/// The standard attributes are looked up through [`AttributeMap`], so that a
+/// repeated one fails with [`ChannelError::DuplicateAttributeKey`] as for the
+/// other events, while unknown attributes are collected into `extra`. When
+/// both the hex-encoded and the deprecated plaintext form of the packet data
+/// (or acknowledgement) are present, the hex-encoded one takes precedence.
+/// The plaintext packet data must then carry the same payload, see
+/// [`PacketDataAttribute::check_consistent_with`].
+///
+/// With the `base64-attributes` feature enabled, the packet data may also be
+/// read from the non-standard `packet_data_b64` attribute, which must then
+/// agree with the hex-encoded or plaintext form if any.
+///
+/// With the `legacy-attributes` feature enabled, the timeout height and
+/// timestamp missing from the event are taken from the combined
+/// `packet_timeout` attribute, see [`LegacyPacketTimeoutAttribute`].
+#[derive(Default)]
+pub(super) struct PacketEventAttributes {
+    pub packet_data: Option<PacketDataAttribute>,
+    pub timeout_height: Option<TimeoutHeightAttribute>,
+    pub timeout_timestamp: Option<TimeoutTimestampAttribute>,
+    pub sequence: Option<SequenceAttribute>,
+    pub src_port_id: Option<SrcPortIdAttribute>,
+    pub src_channel_id: Option<SrcChannelIdAttribute>,
+    pub dst_port_id: Option<DstPortIdAttribute>,
+    pub dst_channel_id: Option<DstChannelIdAttribute>,
+    pub channel_ordering: Option<ChannelOrderingAttribute>,
+    pub connection_id: Option<PacketConnectionIdAttribute>,
+    pub acknowledgement: Option<AcknowledgementAttribute>,
+    pub module: Option<ModuleAttribute>,
+    pub extra: Vec<abci::EventAttribute>,
+}
+
+impl PacketEventAttributes {
+    pub fn parse(event: &abci::Event, expected_kind: &str) -> Result<Self, ChannelError> {
+        let (attrs, errors) = Self::parse_lenient(event, expected_kind);
+
+        match errors.into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(attrs),
+        }
+    }
+
+    /// Parses every attribute it can, collecting the errors of the others
+    /// rather than failing on the first one.
+    pub fn parse_lenient(event: &abci::Event, expected_kind: &str) -> (Self, Vec<ChannelError>) {
+        let mut attrs = Self::default();
+        let mut errors = Vec::new();
+
+        if canonical_event_kind(&event.kind) != expected_kind {
+            errors.push(ChannelError::Other {
+                description: format!("Error in parsing `{expected_kind}` event"),
+            });
+            return (attrs, errors);
+        }
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "parse_packet_event",
+            kind = %event.kind,
+            channel = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+
+        let map = AttributeMap::from(event.attributes.as_slice());
+
+        attrs.packet_data = collect(map.get_keyed(PKT_DATA_HEX_ATTRIBUTE_KEY), &mut errors);
+        let plaintext_packet_data: Option<PacketDataAttribute> =
+            collect(map.get_keyed(PKT_DATA_ATTRIBUTE_KEY), &mut errors);
+        #[cfg(feature = "base64-attributes")]
+        let base64_packet_data = collect(
+            map.find(PKT_DATA_B64_ATTRIBUTE_KEY).and_then(|attribute| {
+                attribute
+                    .map(|attribute| {
+                        PacketDataAttribute::try_from_base64((
+                            attribute.key_bytes(),
+                            attribute.value_bytes(),
+                        ))
+                    })
+                    .transpose()
+            }),
+            &mut errors,
+        );
+        attrs.timeout_height = collect(map.get_optional(), &mut errors);
+        attrs.timeout_timestamp = collect(map.get_optional(), &mut errors);
+        #[cfg(feature = "legacy-attributes")]
+        let legacy_timeout: Option<LegacyPacketTimeoutAttribute> =
+            collect(map.get_keyed(PKT_LEGACY_TIMEOUT_ATTRIBUTE_KEY), &mut errors);
+        attrs.sequence = collect(map.get_optional(), &mut errors);
+        attrs.src_port_id = collect(map.get_optional(), &mut errors);
+        attrs.src_channel_id = collect(map.get_optional(), &mut errors);
+        attrs.dst_port_id = collect(map.get_optional(), &mut errors);
+        attrs.dst_channel_id = collect(map.get_optional(), &mut errors);
+        attrs.channel_ordering = collect(map.get_optional(), &mut errors);
+        attrs.connection_id = collect(map.get_optional(), &mut errors);
+        attrs.acknowledgement = match map.get_keyed(PKT_ACK_HEX_ATTRIBUTE_KEY) {
+            Ok(None) => collect(map.get_keyed(PKT_ACK_ATTRIBUTE_KEY), &mut errors),
+            hex => collect(hex, &mut errors),
+        };
+        attrs.module = collect(map.get_optional(), &mut errors);
+
+        for attribute in &event.attributes {
+            match attribute.key_str() {
+                Ok(key) if packet_event_attribute_key(key).is_some() => {}
+                Ok(_) => attrs.extra.push(attribute.clone()),
+                Err(_) => errors.push(record_parse_error(ChannelError::InvalidAttributeKey {
+                    attribute_key: String::new(),
+                })),
+            }
+        }
+
+        #[cfg(feature = "legacy-attributes")]
+        if let Some(legacy_timeout) = legacy_timeout {
+            let (timeout_height, timeout_timestamp) = legacy_timeout.split();
+            attrs.timeout_height.get_or_insert(timeout_height);
+            attrs.timeout_timestamp.get_or_insert(timeout_timestamp);
+        }
+
+        if let Some(plaintext) = plaintext_packet_data {
+            match &attrs.packet_data {
+                // Binary payloads emitted with `into_lossy_attributes` only
+                // carry their exact bytes in `packet_data_hex`.
+                Some(hex) if plaintext.is_lossy_rendering_of(hex) => {}
+                Some(hex) => {
+                    if let Err(err) = plaintext.check_consistent_with(hex) {
+                        errors.push(err);
+                    }
+                }
+                None => attrs.packet_data = Some(plaintext),
+            }
+        }
+
+        #[cfg(feature = "base64-attributes")]
+        if let Some(base64_data) = base64_packet_data {
+            match &attrs.packet_data {
+                Some(packet_data) => {
+                    if let Err(err) = base64_data.check_consistent_with(packet_data) {
+                        errors.push(err);
+                    }
+                }
+                None => attrs.packet_data = Some(base64_data),
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        attrs.trace(&span, event);
+
+        (attrs, errors)
+    }
+
+    /// Records the channel of the event on its parsing span, and warns about
+    /// the anomalies the parser recovered from: repeated non-standard
+    /// attribute keys, and deprecated attributes emitted without their
+    /// replacement.
+    #[cfg(feature = "tracing")]
+    fn trace(&self, span: &tracing::Span, event: &abci::Event) {
+        if let Some(attr) = &self.src_channel_id {
+            span.record("channel", tracing::field::display(&attr.src_channel_id));
+        }
+
+        let mut seen_keys = BTreeSet::new();
+        for attribute in &event.attributes {
+            let Ok(key) = attribute.key_str() else {
+                continue;
+            };
+            if !seen_keys.insert(key) && packet_event_attribute_key(key).is_none() {
+                tracing::warn!(key, "repeated attribute key");
+            }
+        }
+
+        for (deprecated, replacement) in [
+            (PKT_DATA_ATTRIBUTE_KEY, PKT_DATA_HEX_ATTRIBUTE_KEY),
+            (PKT_ACK_ATTRIBUTE_KEY, PKT_ACK_HEX_ATTRIBUTE_KEY),
+        ] {
+            if seen_keys.contains(deprecated) && !seen_keys.contains(replacement) {
+                tracing::warn!(
+                    key = deprecated,
+                    replacement,
+                    "deprecated attribute without its replacement"
+                );
+            }
+        }
+    }
+}
+
+/// Keeps the parsed attribute, if any, collecting the error otherwise.
+fn collect<T>(
+    result: Result<Option<T>, ChannelError>,
+    errors: &mut Vec<ChannelError>,
+) -> Option<T> {
+    result.unwrap_or_else(|err| {
+        errors.push(err);
+        None
+    })
+}
+
+/// Unwraps a parsed attribute, failing if the event did not carry it.
+pub(super) fn required_attribute<T>(attr: Option<T>, key: &str) -> Result<T, ChannelError> {
+    attr.ok_or_else(|| missing_attribute_key(key))
+}
+
+#[cfg(all(test, any(feature = "borsh", feature = "parity-scale-codec")))]
+mod codec_tests {
+    use core::fmt::Debug;
+
+    use proptest::prelude::*;
+
+    use super::super::channel_attributes::{
+        ChannelIdAttribute, ConnectionIdAttribute, PortIdAttribute, VersionAttribute,
+    };
+    use super::*;
+    use crate::Version;
+
+    fn sequence() -> impl Strategy<Value = Sequence> {
+        prop_oneof![Just(0u64), any::<u64>()].prop_map(Sequence::from)
+    }
+
+    fn timeout_height() -> impl Strategy<Value = TimeoutHeight> {
+        prop_oneof![
+            Just(TimeoutHeight::Never),
+            (any::<u64>(), 1..=u64::MAX).prop_map(|(revision_number, revision_height)| {
+                TimeoutHeight::At(Height::new(revision_number, revision_height).unwrap())
+            }),
+        ]
+    }
+
+    fn timestamp() -> impl Strategy<Value = Timestamp> {
+        prop_oneof![Just(0u64), any::<u64>()]
+            .prop_map(|nanoseconds| Timestamp::from_nanoseconds(nanoseconds).unwrap())
+    }
+
+    fn version() -> impl Strategy<Value = Version> {
+        prop_oneof![Just(String::new()), ".*"].prop_map(Version::new)
+    }
+
+    fn order() -> impl Strategy<Value = Order> {
+        prop_oneof![
+            Just(Order::None),
+            Just(Order::Unordered),
+            Just(Order::Ordered)
+        ]
+    }
+
+    #[cfg(feature = "borsh")]
+    fn borsh_round_trip<T>(value: &T)
+    where
+        T: borsh::BorshSerialize + borsh::BorshDeserialize + PartialEq + Debug,
+    {
+        let bytes = borsh::to_vec(value).unwrap();
+        assert_eq!(&borsh::from_slice::<T>(&bytes).unwrap(), value);
+    }
+
+    #[cfg(feature = "parity-scale-codec")]
+    fn scale_round_trip<T>(value: &T)
+    where
+        T: parity_scale_codec::Encode + parity_scale_codec::Decode + PartialEq + Debug,
+    {
+        let bytes = value.encode();
+        assert_eq!(&T::decode(&mut bytes.as_slice()).unwrap(), value);
+    }
+
+    /// Round-trips a value through every codec enabled for the build.
+    macro_rules! round_trip {
+        ($value:expr) => {{
+            let value = $value;
+            #[cfg(feature = "borsh")]
+            borsh_round_trip(&value);
+            #[cfg(feature = "parity-scale-codec")]
+            scale_round_trip(&value);
+        }};
+    }
+
+    proptest! {
+        #[test]
+        fn packet_attributes_round_trip(
+            packet_data in proptest::collection::vec(any::<u8>(), 0..64),
+            timeout_height in timeout_height(),
+            timeout_timestamp in timestamp(),
+            sequence in sequence(),
+            channel_number in any::<u64>(),
+            order in order(),
+        ) {
+            round_trip!(PacketDataAttribute { packet_data });
+            round_trip!(TimeoutHeightAttribute { timeout_height });
+            round_trip!(TimeoutTimestampAttribute { timeout_timestamp });
+            round_trip!(SequenceAttribute { sequence });
+            round_trip!(SrcPortIdAttribute::from(PortId::transfer()));
+            round_trip!(SrcChannelIdAttribute::from(ChannelId::new(channel_number)));
+            round_trip!(DstPortIdAttribute::from(PortId::transfer()));
+            round_trip!(DstChannelIdAttribute::from(ChannelId::new(channel_number)));
+            round_trip!(ChannelOrderingAttribute { order });
+            round_trip!(PacketConnectionIdAttribute::from(ConnectionId::new(channel_number)));
+        }
+
+        #[test]
+        fn channel_attributes_round_trip(
+            channel_number in any::<u64>(),
+            version in version(),
+        ) {
+            round_trip!(PortIdAttribute::from(PortId::transfer()));
+            round_trip!(ChannelIdAttribute::from(ChannelId::new(channel_number)));
+            round_trip!(ConnectionIdAttribute::from(ConnectionId::new(channel_number)));
+            round_trip!(VersionAttribute { version });
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Detection of changes in the attribute layout of channel events across a
+//! stream of blocks, e.g. as a result of a chain upgrade.
+use ibc_core_host_types::identifiers::ChannelId;
+use ibc_primitives::prelude::*;
+use tendermint::abci;
+
+use super::channel_attributes::CHANNEL_ID_ATTRIBUTE_KEY;
+use super::packet_attributes::PKT_SRC_CHANNEL_ATTRIBUTE_KEY;
+
+/// Reports that the schema, i.e. the ordered list of attribute keys, of an
+/// event kind changed for a given channel between two consecutive events.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaDrift {
+    pub channel_id: ChannelId,
+    pub event_kind: String,
+    pub previous_schema: Vec<String>,
+    pub current_schema: Vec<String>,
+}
+
+/// Tracks the schema of the events observed on each channel and flags when
+/// it changes between consecutive events of the same kind.
+///
+/// Events that do not carry a channel identifier, such as client or
+/// connection events, are ignored.
+#[derive(Clone, Debug, Default)]
+pub struct SchemaDriftDetector {
+    schemas: BTreeMap<(ChannelId, String), Vec<String>>,
+}
+
+impl SchemaDriftDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the schema of the given event, returning a [`SchemaDrift`] if
+    /// it differs from the last one observed for the same channel and kind.
+    pub fn observe(&mut self, event: &abci::Event) -> Option<SchemaDrift> {
+        let channel_id = event_channel_id(event)?;
+        let current_schema: Vec<String> = event
+            .attributes
+            .iter()
+            .filter_map(|attr| attr.key_str().ok().map(ToString::to_string))
+            .collect();
+
+        let previous_schema = self.schemas.insert(
+            (channel_id.clone(), event.kind.clone()),
+            current_schema.clone(),
+        )?;
+
+        (previous_schema != current_schema).then(|| SchemaDrift {
+            channel_id,
+            event_kind: event.kind.clone(),
+            previous_schema,
+            current_schema,
+        })
+    }
+
+    /// Returns the last schema observed for the given channel and event kind.
+    pub fn schema(&self, channel_id: &ChannelId, event_kind: &str) -> Option<&[String]> {
+        self.schemas
+            .get(&(channel_id.clone(), event_kind.to_string()))
+            .map(Vec::as_slice)
+    }
+}
+
+/// Extracts the channel the event refers to: the source channel for packet
+/// events, or the local channel for handshake events.
+fn event_channel_id(event: &abci::Event) -> Option<ChannelId> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| {
+            matches!(
+                attr.key_str(),
+                Ok(PKT_SRC_CHANNEL_ATTRIBUTE_KEY) | Ok(CHANNEL_ID_ATTRIBUTE_KEY)
+            )
+        })
+        .and_then(|attr| attr.value_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send_packet_event(channel: &str, with_plaintext_data: bool) -> abci::Event {
+        let mut attributes: Vec<abci::EventAttribute> = Vec::new();
+        if with_plaintext_data {
+            attributes.push(("packet_data", "data").into());
+        }
+        attributes.push(("packet_data_hex", "64617461").into());
+        attributes.push(("packet_sequence", "1").into());
+        attributes.push(("packet_src_channel", channel).into());
+
+        abci::Event {
+            kind: "send_packet".to_string(),
+            attributes,
+        }
+    }
+
+    #[test]
+    fn detect_mid_stream_schema_change() {
+        let mut detector = SchemaDriftDetector::new();
+
+        assert!(detector
+            .observe(&send_packet_event("channel-0", true))
+            .is_none());
+        assert!(detector
+            .observe(&send_packet_event("channel-0", true))
+            .is_none());
+        // A different channel keeps its own schema.
+        assert!(detector
+            .observe(&send_packet_event("channel-1", false))
+            .is_none());
+
+        // The chain upgrade drops the deprecated plaintext data attribute.
+        let drift = detector
+            .observe(&send_packet_event("channel-0", false))
+            .expect("schema drift must be detected");
+        assert_eq!(drift.channel_id, ChannelId::zero());
+        assert_eq!(drift.event_kind, "send_packet");
+        assert_eq!(drift.previous_schema.len(), 4);
+        assert_eq!(drift.current_schema.len(), 3);
+
+        // The new schema becomes the reference for subsequent events.
+        assert!(detector
+            .observe(&send_packet_event("channel-0", false))
+            .is_none());
+        assert_eq!(
+            detector
+                .schema(&ChannelId::zero(), "send_packet")
+                .map(<[String]>::len),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn events_without_channel_are_ignored() {
+        let mut detector = SchemaDriftDetector::new();
+        let event = abci::Event {
+            kind: "create_client".to_string(),
+            attributes: vec![("client_id", "07-tendermint-0").into()],
+        };
+
+        assert!(detector.observe(&event).is_none());
+        assert!(detector.observe(&event).is_none());
+    }
+}
@@ -0,0 +1,121 @@
+//! A stable, versioned serde representation of `SendPacket` events, for event
+//! stores persisting them across upgrades of this crate.
+//!
+//! Unlike the serde representation derived on `SendPacket`, which follows its
+//! internal layout, documents of a given version keep their schema: every
+//! value is the string value of the corresponding ABCI attribute, and
+//! attributes added later are optional, so that older documents keep
+//! deserializing and unknown fields of newer ones are ignored. Breaking
+//! changes, if ever needed, go into a new version.
+use ibc_primitives::prelude::*;
+use serde::{Deserialize, Serialize};
+use tendermint::abci;
+
+use super::packet_attributes::{
+    PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY, PKT_CONNECTION_ID_ATTRIBUTE_KEY,
+    PKT_DATA_HEX_ATTRIBUTE_KEY, PKT_DST_CHANNEL_ATTRIBUTE_KEY, PKT_DST_PORT_ATTRIBUTE_KEY,
+    PKT_SEQ_ATTRIBUTE_KEY, PKT_SRC_CHANNEL_ATTRIBUTE_KEY, PKT_SRC_PORT_ATTRIBUTE_KEY,
+    PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY, PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+};
+use super::{EmissionProfile, ModuleAttribute, SendPacket, SEND_PACKET_EVENT};
+use crate::error::ChannelError;
+
+/// A `SendPacket` event tagged with the version of its schema, e.g.
+/// `{"version":"1","packet_data_hex":"...",...}`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "version")]
+pub enum VersionedSendPacket {
+    #[serde(rename = "1")]
+    V1(SendPacketV1),
+}
+
+/// Version 1 of the schema, holding the attributes emitted by ibc-go.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SendPacketV1 {
+    pub packet_data_hex: String,
+    pub packet_timeout_height: String,
+    pub packet_timeout_timestamp: String,
+    pub packet_sequence: String,
+    pub packet_src_port: String,
+    pub packet_src_channel: String,
+    pub packet_dst_port: String,
+    pub packet_dst_channel: String,
+    pub packet_channel_ordering: String,
+    pub packet_connection: String,
+    /// Optional, as not every chain attaches the name of its module to the
+    /// event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+}
+
+impl SendPacket {
+    /// Converts the event into the latest version of its stable serde
+    /// representation. Non-standard attributes are left out.
+    pub fn to_versioned(&self) -> Result<VersionedSendPacket, ChannelError> {
+        // Only the hex-encoded packet data is kept, so that non UTF-8 payloads
+        // can be represented.
+        let mut attributes = self
+            .packet_data_attr
+            .clone()
+            .into_attributes(EmissionProfile::IbcGoV10)?;
+        attributes.append(&mut self.non_data_attributes());
+
+        let value = |key: &str| {
+            attributes
+                .iter()
+                .find(|attr| attr.key_bytes() == key.as_bytes())
+                .and_then(|attr| attr.value_str().ok())
+                .map(ToString::to_string)
+                .ok_or_else(|| ChannelError::MissingAttributeKey {
+                    attribute_key: key.to_string(),
+                })
+        };
+
+        Ok(VersionedSendPacket::V1(SendPacketV1 {
+            packet_data_hex: value(PKT_DATA_HEX_ATTRIBUTE_KEY)?,
+            packet_timeout_height: value(PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY)?,
+            packet_timeout_timestamp: value(PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY)?,
+            packet_sequence: value(PKT_SEQ_ATTRIBUTE_KEY)?,
+            packet_src_port: value(PKT_SRC_PORT_ATTRIBUTE_KEY)?,
+            packet_src_channel: value(PKT_SRC_CHANNEL_ATTRIBUTE_KEY)?,
+            packet_dst_port: value(PKT_DST_PORT_ATTRIBUTE_KEY)?,
+            packet_dst_channel: value(PKT_DST_CHANNEL_ATTRIBUTE_KEY)?,
+            packet_channel_ordering: value(PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY)?,
+            packet_connection: value(PKT_CONNECTION_ID_ATTRIBUTE_KEY)?,
+            module: self.module().map(ToString::to_string),
+        }))
+    }
+
+    /// Parses the event back from any version of its stable serde
+    /// representation, with the same checks as when parsing an ABCI event.
+    pub fn from_versioned(versioned: VersionedSendPacket) -> Result<Self, ChannelError> {
+        let VersionedSendPacket::V1(v1) = versioned;
+
+        let mut attributes: Vec<abci::EventAttribute> = vec![
+            (PKT_DATA_HEX_ATTRIBUTE_KEY, v1.packet_data_hex).into(),
+            (PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY, v1.packet_timeout_height).into(),
+            (
+                PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+                v1.packet_timeout_timestamp,
+            )
+                .into(),
+            (PKT_SEQ_ATTRIBUTE_KEY, v1.packet_sequence).into(),
+            (PKT_SRC_PORT_ATTRIBUTE_KEY, v1.packet_src_port).into(),
+            (PKT_SRC_CHANNEL_ATTRIBUTE_KEY, v1.packet_src_channel).into(),
+            (PKT_DST_PORT_ATTRIBUTE_KEY, v1.packet_dst_port).into(),
+            (PKT_DST_CHANNEL_ATTRIBUTE_KEY, v1.packet_dst_channel).into(),
+            (
+                PKT_CHANNEL_ORDERING_ATTRIBUTE_KEY,
+                v1.packet_channel_ordering,
+            )
+                .into(),
+            (PKT_CONNECTION_ID_ATTRIBUTE_KEY, v1.packet_connection).into(),
+        ];
+        attributes.extend(v1.module.map(|module| ModuleAttribute { module }.into()));
+
+        SendPacket::try_from(abci::Event {
+            kind: SEND_PACKET_EVENT.to_string(),
+            attributes,
+        })
+    }
+}
@@ -26,6 +26,8 @@ pub mod timeout;
 
 pub mod acknowledgement;
 pub mod commitment;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 mod version;
 pub use version::Version;
 
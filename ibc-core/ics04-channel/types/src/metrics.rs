@@ -0,0 +1,116 @@
+//! Hooks for counting event attribute parse failures.
+//!
+//! A [`MetricsRecorder`] can be installed with [`set_recorder`]. Until then,
+//! failures are not recorded anywhere.
+use std::sync::RwLock;
+
+use ibc_primitives::prelude::*;
+
+use crate::error::ChannelError;
+
+/// Name of the counter incremented on every attribute parse failure. It is
+/// labeled with the `kind` of failure: `invalid_key`, `invalid_value`,
+/// `missing_key`, `duplicate_key`, `invalid_identifier` or `other`.
+pub const ATTRIBUTE_PARSE_ERROR_COUNTER: &str = "ibc_attribute_parse_error";
+
+/// Bridge to the metrics backend of the host application.
+pub trait MetricsRecorder: Send + Sync {
+    /// Increments the counter `name` with the given `labels` by one.
+    fn increment_counter(&self, name: &'static str, labels: &[(&'static str, &'static str)]);
+}
+
+static RECORDER: RwLock<Option<Box<dyn MetricsRecorder>>> = RwLock::new(None);
+
+/// Installs the recorder notified of attribute parse failures, replacing any
+/// previously installed one.
+pub fn set_recorder(recorder: Box<dyn MetricsRecorder>) {
+    if let Ok(mut guard) = RECORDER.write() {
+        *guard = Some(recorder);
+    }
+}
+
+/// Uninstalls the current recorder, if any.
+pub fn clear_recorder() {
+    if let Ok(mut guard) = RECORDER.write() {
+        *guard = None;
+    }
+}
+
+pub(crate) fn record_attribute_parse_error(err: &ChannelError) {
+    let kind = match err {
+        ChannelError::InvalidAttributeKey { .. } => "invalid_key",
+        ChannelError::InvalidAttributeValue { .. }
+        | ChannelError::InvalidHexAttributeValue { .. } => "invalid_value",
+        ChannelError::MissingAttributeKey { .. } => "missing_key",
+        ChannelError::DuplicateAttributeKey { .. } => "duplicate_key",
+        ChannelError::InvalidIdentifier(_) | ChannelError::NonCanonicalChannelId { .. } => {
+            "invalid_identifier"
+        }
+        _ => "other",
+    };
+
+    if let Ok(guard) = RECORDER.read() {
+        if let Some(recorder) = guard.as_ref() {
+            recorder.increment_counter(ATTRIBUTE_PARSE_ERROR_COUNTER, &[("kind", kind)]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    use tendermint::abci;
+
+    use super::*;
+    use crate::events::packet_attributes::{PacketDataAttribute, SequenceAttribute};
+    use crate::events::SendPacket;
+
+    #[derive(Default)]
+    struct MockRecorder {
+        invalid_values: Arc<AtomicU64>,
+        missing_keys: Arc<AtomicU64>,
+    }
+
+    impl MetricsRecorder for MockRecorder {
+        fn increment_counter(&self, name: &'static str, labels: &[(&'static str, &'static str)]) {
+            if name != ATTRIBUTE_PARSE_ERROR_COUNTER {
+                return;
+            }
+            match labels {
+                [("kind", "invalid_value")] => self.invalid_values.fetch_add(1, Ordering::SeqCst),
+                [("kind", "missing_key")] => self.missing_keys.fetch_add(1, Ordering::SeqCst),
+                _ => 0,
+            };
+        }
+    }
+
+    #[test]
+    fn malformed_attribute_increments_counter() {
+        let recorder = MockRecorder::default();
+        let (invalid_values, missing_keys) = (
+            recorder.invalid_values.clone(),
+            recorder.missing_keys.clone(),
+        );
+        set_recorder(Box::new(recorder));
+
+        let malformed: abci::EventAttribute = ("packet_sequence", "not-a-number").into();
+        assert!(SequenceAttribute::try_from(malformed).is_err());
+        assert!(invalid_values.load(Ordering::SeqCst) >= 1);
+
+        let invalid_values_before = invalid_values.load(Ordering::SeqCst);
+        let malformed_hex: abci::EventAttribute = ("packet_data_hex", "zz").into();
+        assert!(PacketDataAttribute::try_from(malformed_hex).is_err());
+        assert!(invalid_values.load(Ordering::SeqCst) > invalid_values_before);
+
+        let empty = abci::Event {
+            kind: "send_packet".to_string(),
+            attributes: vec![],
+        };
+        assert!(SendPacket::try_from(empty).is_err());
+        assert!(missing_keys.load(Ordering::SeqCst) >= 1);
+
+        clear_recorder();
+    }
+}
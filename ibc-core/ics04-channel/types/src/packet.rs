@@ -5,6 +5,7 @@ use ibc_primitives::prelude::*;
 use ibc_primitives::Expiry::Expired;
 use ibc_primitives::Timestamp;
 use ibc_proto::ibc::core::channel::v1::{Packet as RawPacket, PacketState as RawPacketState};
+use subtle_encoding::hex;
 
 use super::timeout::TimeoutHeight;
 use crate::error::PacketError;
@@ -145,6 +146,44 @@ impl Packet {
 
         height_timed_out || timestamp_timed_out
     }
+
+    /// Identifies the packet on its source chain.
+    pub fn id(&self) -> PacketId {
+        PacketId {
+            port_id: self.port_id_on_a.clone(),
+            channel_id: self.chan_id_on_a.clone(),
+            sequence: self.seq_on_a,
+        }
+    }
+
+    /// Returns the canonical JSON representation of the packet, suitable for
+    /// off-chain signing or logging.
+    ///
+    /// Keys are sorted lexicographically, no insignificant whitespace is
+    /// emitted, integers are encoded as strings to avoid precision loss, and
+    /// the packet data is lowercase hex-encoded. A timeout height of "never"
+    /// is encoded as the zero height.
+    pub fn to_canonical_json(&self) -> String {
+        let data_hex = hex::encode(&self.data);
+
+        format!(
+            concat!(
+                r#"{{"data":"{}","destination_channel":"{}","destination_port":"{}","#,
+                r#""sequence":"{}","source_channel":"{}","source_port":"{}","#,
+                r#""timeout_height":{{"revision_height":"{}","revision_number":"{}"}},"#,
+                r#""timeout_timestamp":"{}"}}"#
+            ),
+            str::from_utf8(&data_hex).expect("Never fails because hexadecimal is valid UTF-8"),
+            self.chan_id_on_b,
+            self.port_id_on_b,
+            self.seq_on_a,
+            self.chan_id_on_a,
+            self.port_id_on_a,
+            self.timeout_height_on_b.commitment_revision_height(),
+            self.timeout_height_on_b.commitment_revision_number(),
+            self.timeout_timestamp_on_b.nanoseconds(),
+        )
+    }
 }
 
 /// Custom debug output to omit the packet data
@@ -225,6 +264,152 @@ impl From<Packet> for RawPacket {
     }
 }
 
+/// Identifies a packet by its source port, source channel and sequence.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PacketId {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+}
+
+impl PacketId {
+    /// Encodes the packet id into a compact binary key: the length-prefixed
+    /// port and channel identifiers, followed by the 8-byte big-endian
+    /// sequence, so that keys of a channel sort by sequence.
+    pub fn to_key_bytes(&self) -> Vec<u8> {
+        let port_id = self.port_id.as_str().as_bytes();
+        let channel_id = self.channel_id.as_str().as_bytes();
+
+        let mut key = Vec::with_capacity(2 + port_id.len() + channel_id.len() + 8);
+        // Identifiers are at most 128 characters long, so their length always
+        // fits into a single byte.
+        key.push(port_id.len() as u8);
+        key.extend_from_slice(port_id);
+        key.push(channel_id.len() as u8);
+        key.extend_from_slice(channel_id);
+        key.extend_from_slice(&self.sequence.value().to_be_bytes());
+        key
+    }
+
+    /// Decodes a key produced by [`PacketId::to_key_bytes`].
+    pub fn from_key_bytes(key: &[u8]) -> Result<Self, PacketError> {
+        let (port_id, rest) = split_length_prefixed(key)?;
+        let (channel_id, sequence) = split_length_prefixed(rest)?;
+
+        let sequence: [u8; 8] =
+            sequence
+                .try_into()
+                .map_err(|_| PacketError::MalformedPacketIdKey {
+                    reason: format!("expected 8 sequence bytes, got {}", sequence.len()),
+                })?;
+
+        Ok(Self {
+            port_id: port_id.parse()?,
+            channel_id: channel_id.parse()?,
+            sequence: u64::from_be_bytes(sequence).into(),
+        })
+    }
+}
+
+/// Splits a length-prefixed identifier off the front of `bytes`.
+fn split_length_prefixed(bytes: &[u8]) -> Result<(&str, &[u8]), PacketError> {
+    let (len, rest) = bytes
+        .split_first()
+        .ok_or_else(|| PacketError::MalformedPacketIdKey {
+            reason: "missing identifier length".to_string(),
+        })?;
+
+    if rest.len() < usize::from(*len) {
+        return Err(PacketError::MalformedPacketIdKey {
+            reason: format!("identifier truncated to {} bytes", rest.len()),
+        });
+    }
+
+    let (identifier, rest) = rest.split_at(usize::from(*len));
+    let identifier =
+        core::str::from_utf8(identifier).map_err(|_| PacketError::MalformedPacketIdKey {
+            reason: "identifier is not valid UTF-8".to_string(),
+        })?;
+
+    Ok((identifier, rest))
+}
+
+/// A half-open range `[start, end)` of packet sequences, e.g. of the packets
+/// a relayer pulls in a single batch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SequenceRange {
+    start: Sequence,
+    end: Sequence,
+}
+
+impl SequenceRange {
+    /// Builds the range of sequences from `start` up to, but excluding, `end`.
+    pub fn new(start: Sequence, end: Sequence) -> Result<Self, PacketError> {
+        if end < start {
+            return Err(PacketError::InvalidSequenceRange { start, end });
+        }
+
+        Ok(Self { start, end })
+    }
+
+    /// Builds the range of the `len` sequences starting at `start`, failing
+    /// if the end of the range overflows.
+    pub fn with_len(start: Sequence, len: u64) -> Result<Self, PacketError> {
+        Ok(Self {
+            start,
+            end: start.increment_by(len)?,
+        })
+    }
+
+    pub fn start(&self) -> Sequence {
+        self.start
+    }
+
+    pub fn end(&self) -> Sequence {
+        self.end
+    }
+
+    /// The number of sequences in the range.
+    pub fn len(&self) -> u64 {
+        self.end.value() - self.start.value()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, sequence: &Sequence) -> bool {
+        self.start <= *sequence && *sequence < self.end
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Sequence> {
+        self.into_iter()
+    }
+}
+
+impl IntoIterator for SequenceRange {
+    type Item = Sequence;
+    type IntoIter = core::iter::Map<core::ops::Range<u64>, fn(u64) -> Sequence>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (self.start.value()..self.end.value()).map(Sequence::from as fn(u64) -> Sequence)
+    }
+}
+
 /// The packet state type.
 ///
 /// Each application defines the structure of the `data` field.
@@ -309,3 +494,120 @@ impl From<PacketState> for RawPacketState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ibc_core_host_types::error::IdentifierError;
+
+    use super::*;
+
+    #[test]
+    fn canonical_json_is_deterministic() {
+        let packet = Packet {
+            seq_on_a: 1.into(),
+            port_id_on_a: PortId::transfer(),
+            chan_id_on_a: ChannelId::zero(),
+            port_id_on_b: PortId::transfer(),
+            chan_id_on_b: ChannelId::new(1),
+            data: b"hi".to_vec(),
+            timeout_height_on_b: Height::new(1, 10).unwrap().into(),
+            timeout_timestamp_on_b: Timestamp::none(),
+        };
+
+        let expected = concat!(
+            r#"{"data":"6869","destination_channel":"channel-1","destination_port":"transfer","#,
+            r#""sequence":"1","source_channel":"channel-0","source_port":"transfer","#,
+            r#""timeout_height":{"revision_height":"10","revision_number":"1"},"#,
+            r#""timeout_timestamp":"0"}"#
+        );
+
+        assert_eq!(packet.to_canonical_json(), expected);
+        assert_eq!(
+            packet.clone().to_canonical_json(),
+            packet.to_canonical_json()
+        );
+    }
+
+    #[test]
+    fn packet_id_key_bytes_round_trip() {
+        let packet_id = PacketId {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(12),
+            sequence: 258.into(),
+        };
+
+        let key = packet_id.to_key_bytes();
+        assert_eq!(key[0], 8);
+        assert_eq!(&key[key.len() - 8..], &[0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(PacketId::from_key_bytes(&key).unwrap(), packet_id);
+
+        assert!(matches!(
+            PacketId::from_key_bytes(&key[..key.len() - 1]),
+            Err(PacketError::MalformedPacketIdKey { .. })
+        ));
+        assert!(matches!(
+            PacketId::from_key_bytes(&[20, b'x']),
+            Err(PacketError::MalformedPacketIdKey { .. })
+        ));
+    }
+
+    #[test]
+    fn empty_sequence_range() {
+        let range = SequenceRange::new(5.into(), 5.into()).unwrap();
+
+        assert!(range.is_empty());
+        assert_eq!(range.len(), 0);
+        assert!(!range.contains(&5.into()));
+        assert_eq!(range.iter().count(), 0);
+    }
+
+    #[test]
+    fn single_sequence_range() {
+        let range = SequenceRange::new(5.into(), 6.into()).unwrap();
+
+        assert!(!range.is_empty());
+        assert_eq!(range.len(), 1);
+        assert!(range.contains(&5.into()));
+        assert!(!range.contains(&6.into()));
+        assert_eq!(
+            range.into_iter().collect::<Vec<_>>(),
+            vec![Sequence::from(5)]
+        );
+    }
+
+    #[test]
+    fn multi_sequence_range() {
+        let range = SequenceRange::new(1.into(), 4.into()).unwrap();
+
+        assert_eq!(range.len(), 3);
+        assert!(!range.contains(&0.into()));
+        assert!(range.contains(&3.into()));
+        assert!(!range.contains(&4.into()));
+        assert_eq!(
+            range.iter().collect::<Vec<_>>(),
+            vec![Sequence::from(1), Sequence::from(2), Sequence::from(3)]
+        );
+
+        assert!(matches!(
+            SequenceRange::new(4.into(), 1.into()),
+            Err(PacketError::InvalidSequenceRange { start, end })
+                if start == Sequence::from(4) && end == Sequence::from(1)
+        ));
+    }
+
+    #[test]
+    fn sequence_range_with_len() {
+        let range = SequenceRange::with_len(1.into(), 3).unwrap();
+        assert_eq!(range, SequenceRange::new(1.into(), 4.into()).unwrap());
+
+        let last = SequenceRange::with_len((u64::MAX - 1).into(), 1).unwrap();
+        assert_eq!(last.end(), Sequence::from(u64::MAX));
+
+        assert!(matches!(
+            SequenceRange::with_len(u64::MAX.into(), 1),
+            Err(PacketError::InvalidIdentifier(
+                IdentifierError::SequenceOverflow { .. }
+            ))
+        ));
+    }
+}
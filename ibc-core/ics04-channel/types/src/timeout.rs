@@ -1,6 +1,7 @@
 //! Types and utilities pertaining to packet timeouts.
 
 use core::fmt::{Display, Error as FmtError, Formatter};
+use core::time::Duration;
 
 use ibc_core_client_types::error::ClientError;
 use ibc_core_client_types::Height;
@@ -75,6 +76,32 @@ impl TimeoutHeight {
         }
     }
 
+    /// Estimates the time left until the timeout height is reached from the
+    /// `current` height, assuming blocks are produced every `block_time`.
+    ///
+    /// Returns `None` if there is no timeout height, or if it belongs to a
+    /// different revision than `current`, as block production across a
+    /// revision upgrade cannot be estimated. Estimates beyond `u64::MAX`
+    /// nanoseconds, i.e. more than 584 years, saturate to that duration.
+    pub fn estimated_time_until(&self, current: Height, block_time: Duration) -> Option<Duration> {
+        match self {
+            Self::At(timeout_height)
+                if timeout_height.revision_number() == current.revision_number() =>
+            {
+                let blocks = timeout_height
+                    .revision_height()
+                    .saturating_sub(current.revision_height());
+
+                let nanos = block_time.as_nanos().saturating_mul(u128::from(blocks));
+
+                Some(Duration::from_nanos(
+                    u64::try_from(nanos).unwrap_or(u64::MAX),
+                ))
+            }
+            _ => None,
+        }
+    }
+
     /// Returns a string formatted for an ABCI event attribute value.
     pub fn to_event_attribute_value(self) -> String {
         match self {
@@ -203,3 +230,55 @@ mod tests {
         }
     }
 }
+
+#[cfg(test)]
+mod estimation_tests {
+    use super::*;
+
+    #[test]
+    fn estimated_time_until_same_revision() {
+        let timeout_height = TimeoutHeight::At(Height::new(1, 110).unwrap());
+        let block_time = Duration::from_secs(6);
+
+        assert_eq!(
+            timeout_height.estimated_time_until(Height::new(1, 100).unwrap(), block_time),
+            Some(Duration::from_secs(60))
+        );
+        // Already reached
+        assert_eq!(
+            timeout_height.estimated_time_until(Height::new(1, 120).unwrap(), block_time),
+            Some(Duration::ZERO)
+        );
+        // Cross-revision
+        assert_eq!(
+            timeout_height.estimated_time_until(Height::new(2, 1).unwrap(), block_time),
+            None
+        );
+    }
+
+    #[test]
+    fn estimated_time_until_never() {
+        assert_eq!(
+            TimeoutHeight::Never
+                .estimated_time_until(Height::new(1, 100).unwrap(), Duration::from_secs(6)),
+            None
+        );
+    }
+
+    #[test]
+    fn estimated_time_until_far_timeout() {
+        let timeout_height = TimeoutHeight::At(Height::new(1, u64::MAX).unwrap());
+
+        assert_eq!(
+            timeout_height.estimated_time_until(Height::new(1, 1).unwrap(), Duration::from_secs(1)),
+            Some(Duration::from_nanos(u64::MAX))
+        );
+        assert_eq!(
+            timeout_height.estimated_time_until(
+                Height::new(1, u64::MAX - 1).unwrap(),
+                Duration::from_secs(1)
+            ),
+            Some(Duration::from_secs(1))
+        );
+    }
+}
@@ -16,6 +16,8 @@ pub enum IdentifierError {
     RevisionNumberOverflow,
     /// String `{value}` cannot be converted to packet sequence, error: `{reason}`
     InvalidStringAsSequence { value: String, reason: String },
+    /// packet sequence `{sequence}` overflows when incremented by `{increment}`
+    SequenceOverflow { sequence: u64, increment: u64 },
 }
 
 #[cfg(feature = "std")]
@@ -61,6 +61,28 @@ impl ChannelId {
     pub fn zero() -> Self {
         Self::new(0)
     }
+
+    /// Returns the counter of this identifier, i.e. `N` in `channel-N`, or
+    /// `None` if the identifier is not in this canonical form. Parsing already
+    /// rejects such identifiers, so this only happens for identifiers decoded
+    /// without validation, e.g. a deserialized `channel-007`.
+    ///
+    /// ```
+    /// # use ibc_core_host_types::identifiers::ChannelId;
+    /// let chan_id = ChannelId::new(27);
+    /// assert_eq!(chan_id.sequence(), Some(27));
+    /// ```
+    pub fn sequence(&self) -> Option<u64> {
+        let counter = self
+            .0
+            .strip_prefix(CHANNEL_ID_PREFIX)?
+            .strip_prefix('-')?
+            .parse::<u64>()
+            .ok()?;
+
+        // Rules out e.g. leading zeros, which `parse` accepts.
+        (Self::new(counter) == *self).then_some(counter)
+    }
 }
 
 /// This implementation provides a `to_string` method.
@@ -97,3 +119,27 @@ impl PartialEq<str> for ChannelId {
         self.as_str().eq(other)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rstest::rstest]
+    #[case("channel-0", 0)]
+    #[case("channel-123", 123)]
+    #[case("channel-18446744073709551615", u64::MAX)]
+    fn test_channel_id_sequence(#[case] channel_id: &str, #[case] expected: u64) {
+        let channel_id: ChannelId = channel_id.parse().expect("valid channel id");
+        assert_eq!(channel_id.sequence(), Some(expected));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_non_canonical_channel_id_sequence() {
+        // Deserialization skips validation, so non-canonical ids may show up.
+        for channel_id in ["\"channel-0123\"", "\"channel-abc\"", "\"chan-1\""] {
+            let channel_id: ChannelId = serde_json::from_str(channel_id).expect("valid json");
+            assert_eq!(channel_id.sequence(), None);
+        }
+    }
+}
@@ -49,6 +49,23 @@ impl Sequence {
         Sequence(self.0 + 1)
     }
 
+    /// Increments the sequence number by one, or returns `None` if it would
+    /// overflow.
+    pub fn next_checked(&self) -> Option<Sequence> {
+        self.0.checked_add(1).map(Sequence)
+    }
+
+    /// Increments the sequence number by `n`, failing if it would overflow.
+    pub fn increment_by(&self, n: u64) -> Result<Sequence, IdentifierError> {
+        self.0
+            .checked_add(n)
+            .map(Sequence)
+            .ok_or(IdentifierError::SequenceOverflow {
+                sequence: self.0,
+                increment: n,
+            })
+    }
+
     /// Encodes the sequence number into a byte array in big endian.
     pub fn to_vec(&self) -> Vec<u8> {
         self.0.to_be_bytes().to_vec()
@@ -72,3 +89,34 @@ impl core::fmt::Display for Sequence {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_increments() {
+        assert_eq!(Sequence::from(1).next_checked(), Some(Sequence::from(2)));
+        assert_eq!(
+            Sequence::from(u64::MAX - 1).next_checked(),
+            Some(Sequence::from(u64::MAX))
+        );
+        assert_eq!(Sequence::from(u64::MAX).next_checked(), None);
+
+        assert_eq!(
+            Sequence::from(1).increment_by(0).unwrap(),
+            Sequence::from(1)
+        );
+        assert_eq!(
+            Sequence::from(u64::MAX - 10).increment_by(10).unwrap(),
+            Sequence::from(u64::MAX)
+        );
+        assert!(matches!(
+            Sequence::from(u64::MAX - 10).increment_by(11),
+            Err(IdentifierError::SequenceOverflow {
+                sequence,
+                increment: 11,
+            }) if sequence == u64::MAX - 10
+        ));
+    }
+}
@@ -1,15 +1,22 @@
 //! Defines events emitted during handling of IBC messages
 
+use alloc::collections::BTreeSet;
+
 use displaydoc::Display;
+use ibc_core_channel_types::events::EmissionProfile;
+use ibc_core_channel_types::packet::PacketId;
 use ibc_core_channel_types::{error as channel_error, events as ChannelEvents};
 use ibc_core_client_types::error as client_error;
 use ibc_core_client_types::events::{self as ClientEvents};
 use ibc_core_connection_types::{error as connection_error, events as ConnectionEvents};
 use ibc_core_host_types::error::IdentifierError;
+use ibc_core_host_types::identifiers::{ChannelId, PortId};
 use ibc_core_router_types::event::ModuleEvent;
 use ibc_primitives::prelude::*;
 use ibc_primitives::ParseTimestampError;
+use prost::Message;
 use tendermint::abci;
+use tendermint_proto::v0_38::abci::Event as RawEvent;
 
 /// All error variants related to IBC events
 #[derive(Debug, Display)]
@@ -96,7 +103,41 @@ impl TryFrom<IbcEvent> for abci::Event {
     type Error = Error;
 
     fn try_from(event: IbcEvent) -> Result<Self, Self::Error> {
-        Ok(match event {
+        event.to_abci_event(EmissionProfile::Default)
+    }
+}
+
+impl IbcEvent {
+    /// Builds the ABCI event in the attribute layout of `profile`, `TryFrom`
+    /// using [`EmissionProfile::Default`]. Only packet events differ between
+    /// profiles.
+    pub fn to_abci_event(self, profile: EmissionProfile) -> Result<abci::Event, Error> {
+        Ok(match self {
+            IbcEvent::SendPacket(event) => event.to_abci_event(profile).map_err(Error::Channel)?,
+            IbcEvent::ReceivePacket(event) => {
+                event.to_abci_event(profile).map_err(Error::Channel)?
+            }
+            IbcEvent::WriteAcknowledgement(event) => {
+                event.to_abci_event(profile).map_err(Error::Channel)?
+            }
+            IbcEvent::AcknowledgePacket(event) => {
+                event.to_abci_event(profile).map_err(Error::Channel)?
+            }
+            IbcEvent::TimeoutPacket(event) => {
+                event.to_abci_event(profile).map_err(Error::Channel)?
+            }
+            event => event.to_hex_only_abci_event(),
+        })
+    }
+
+    /// Builds the ABCI event with the packet data and acknowledgements only in
+    /// their hex-encoded form.
+    ///
+    /// Unlike [`IbcEvent::to_abci_event`], this never fails, so that it can
+    /// serve to identify events, e.g. to order or deduplicate them. Events
+    /// other than the packet ones are the same as in any profile.
+    pub fn to_hex_only_abci_event(&self) -> abci::Event {
+        match self.clone() {
             IbcEvent::CreateClient(event) => event.into(),
             IbcEvent::UpdateClient(event) => event.into(),
             IbcEvent::UpgradeClient(event) => event.into(),
@@ -111,22 +152,20 @@ impl TryFrom<IbcEvent> for abci::Event {
             IbcEvent::OpenConfirmChannel(event) => event.into(),
             IbcEvent::CloseInitChannel(event) => event.into(),
             IbcEvent::CloseConfirmChannel(event) => event.into(),
-            IbcEvent::SendPacket(event) => event.try_into().map_err(Error::Channel)?,
-            IbcEvent::ReceivePacket(event) => event.try_into().map_err(Error::Channel)?,
-            IbcEvent::WriteAcknowledgement(event) => event.try_into().map_err(Error::Channel)?,
-            IbcEvent::AcknowledgePacket(event) => event.try_into().map_err(Error::Channel)?,
-            IbcEvent::TimeoutPacket(event) => event.try_into().map_err(Error::Channel)?,
+            IbcEvent::SendPacket(event) => event.to_hex_only_event(),
+            IbcEvent::ReceivePacket(event) => event.to_hex_only_event(),
+            IbcEvent::WriteAcknowledgement(event) => event.to_hex_only_event(),
+            IbcEvent::AcknowledgePacket(event) => event.to_hex_only_event(),
+            IbcEvent::TimeoutPacket(event) => event.to_hex_only_event(),
             IbcEvent::ChannelClosed(event) => event.into(),
             IbcEvent::Module(event) => event.into(),
             IbcEvent::Message(event) => abci::Event {
                 kind: MESSAGE_EVENT.to_string(),
                 attributes: vec![("module", event.module_attribute(), true).into()],
             },
-        })
+        }
     }
-}
 
-impl IbcEvent {
     pub fn event_type(&self) -> &str {
         match self {
             IbcEvent::CreateClient(event) => event.event_type(),
@@ -206,3 +245,647 @@ impl From<ModuleEvent> for IbcEvent {
         IbcEvent::Module(e)
     }
 }
+
+impl From<ChannelEvents::SendPacket> for IbcEvent {
+    fn from(e: ChannelEvents::SendPacket) -> Self {
+        IbcEvent::SendPacket(e)
+    }
+}
+
+impl TryFrom<IbcEvent> for ChannelEvents::SendPacket {
+    type Error = Error;
+
+    fn try_from(event: IbcEvent) -> Result<Self, Self::Error> {
+        match event {
+            IbcEvent::SendPacket(e) => Ok(e),
+            event => Err(Error::IncorrectEventType {
+                event: event.event_type().to_string(),
+            }),
+        }
+    }
+}
+
+/// Rewrites the timeout height revision of a packet event from `from` to
+/// `to`, to replay events captured on one environment (e.g. a testnet)
+/// against another one. Other events are left untouched.
+pub fn remap_revision(event: &mut IbcEvent, from: u64, to: u64) {
+    match event {
+        IbcEvent::SendPacket(ev) => ev.remap_timeout_revision(from, to),
+        IbcEvent::ReceivePacket(ev) => ev.remap_timeout_revision(from, to),
+        IbcEvent::WriteAcknowledgement(ev) => ev.remap_timeout_revision(from, to),
+        IbcEvent::AcknowledgePacket(ev) => ev.remap_timeout_revision(from, to),
+        IbcEvent::TimeoutPacket(ev) => ev.remap_timeout_revision(from, to),
+        _ => {}
+    }
+}
+
+/// Counts the packets sent on each channel over a window of events, keyed by
+/// the source port and channel of the packets.
+pub fn channel_throughput(events: &[IbcEvent]) -> BTreeMap<(PortId, ChannelId), usize> {
+    let mut throughput = BTreeMap::new();
+
+    for event in events {
+        if let IbcEvent::SendPacket(send_packet) = event {
+            let channel = (
+                send_packet.port_id_on_a().clone(),
+                send_packet.chan_id_on_a().clone(),
+            );
+            *throughput.entry(channel).or_insert(0) += 1;
+        }
+    }
+
+    throughput
+}
+
+/// A single field that differs between two observations of an event.
+///
+/// Fields are named after their ABCI attribute keys, except for the event
+/// kind which is reported as `event_type`. A `None` value means the field was
+/// absent from the corresponding event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: String,
+    pub previous: Option<String>,
+    pub current: Option<String>,
+}
+
+/// Enumerates the fields that differ between two observations of an event,
+/// e.g. a packet event re-emitted with a different timeout after a reorg.
+///
+/// Events are compared on the attributes of their
+/// [hex-only](IbcEvent::to_hex_only_abci_event) ABCI form, so that binary
+/// packet data is compared too. Fails if either event carries an attribute
+/// that is not valid UTF-8.
+pub fn event_field_diff(a: &IbcEvent, b: &IbcEvent) -> Result<Vec<FieldChange>, Error> {
+    let mut changes = Vec::new();
+
+    if a.event_type() != b.event_type() {
+        changes.push(FieldChange {
+            field: "event_type".to_string(),
+            previous: Some(a.event_type().to_string()),
+            current: Some(b.event_type().to_string()),
+        });
+    }
+
+    let previous = event_fields(a)?;
+    let mut current = event_fields(b)?;
+
+    for (field, previous_value) in previous {
+        let current_value = current.remove(&field);
+        if current_value.as_ref() != Some(&previous_value) {
+            changes.push(FieldChange {
+                field,
+                previous: Some(previous_value),
+                current: current_value,
+            });
+        }
+    }
+
+    changes.extend(
+        current
+            .into_iter()
+            .map(|(field, current_value)| FieldChange {
+                field,
+                previous: None,
+                current: Some(current_value),
+            }),
+    );
+
+    Ok(changes)
+}
+
+fn event_fields(event: &IbcEvent) -> Result<BTreeMap<String, String>, Error> {
+    event
+        .to_hex_only_abci_event()
+        .attributes
+        .iter()
+        .map(|attr| {
+            let key = attr.key_str().map_err(|_| {
+                Error::Channel(channel_error::ChannelError::InvalidAttributeKey {
+                    attribute_key: String::from_utf8_lossy(attr.key_bytes()).into_owned(),
+                })
+            })?;
+            let value = attr.value_str().map_err(|_| {
+                Error::Channel(channel_error::ChannelError::InvalidAttributeValue {
+                    attribute_value: String::from_utf8_lossy(attr.value_bytes()).into_owned(),
+                    error: None,
+                })
+            })?;
+
+            Ok((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Reconstructs the packet events of a block, pairing each result with the
+/// position of the corresponding event in the input so that failures can be
+/// traced back to the offending event.
+pub fn reconstruct_indexed(
+    events: Vec<abci::Event>,
+) -> Vec<(usize, Result<IbcEvent, channel_error::ChannelError>)> {
+    events
+        .into_iter()
+        .enumerate()
+        .map(|(index, event)| (index, reconstruct_packet_event(event)))
+        .collect()
+}
+
+fn reconstruct_packet_event(event: abci::Event) -> Result<IbcEvent, channel_error::ChannelError> {
+    match ChannelEvents::canonical_event_kind(&event.kind) {
+        ChannelEvents::SEND_PACKET_EVENT => event.try_into().map(IbcEvent::SendPacket),
+        ChannelEvents::RECV_PACKET_EVENT => event.try_into().map(IbcEvent::ReceivePacket),
+        ChannelEvents::WRITE_ACK_EVENT => event.try_into().map(IbcEvent::WriteAcknowledgement),
+        ChannelEvents::ACK_PACKET_EVENT => event.try_into().map(IbcEvent::AcknowledgePacket),
+        ChannelEvents::TIMEOUT_EVENT => event.try_into().map(IbcEvent::TimeoutPacket),
+        kind => Err(channel_error::ChannelError::Other {
+            description: format!("cannot reconstruct `{kind}` event"),
+        }),
+    }
+}
+
+/// Options controlling how [`process_block_events`] handles a block.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProcessOptions {
+    /// Report events of a kind other than the packet ones as errors, instead
+    /// of silently skipping them.
+    pub strict: bool,
+    /// Drop events identical to an earlier event of the block.
+    pub deduplicate: bool,
+    /// Return the events in their [`canonical_event_order`].
+    pub canonical_order: bool,
+}
+
+/// Outcome of [`process_block_events`].
+#[derive(Debug, Default)]
+pub struct ProcessedBlock {
+    /// The reconstructed packet events.
+    pub events: Vec<IbcEvent>,
+    /// The events that could not be reconstructed, along with their position
+    /// in the block.
+    pub errors: Vec<(usize, channel_error::ChannelError)>,
+    /// Number of events skipped for not being packet events.
+    pub skipped: usize,
+    /// Number of events dropped as duplicates.
+    pub duplicates: usize,
+}
+
+/// Reconstructs the packet events of a block in one pass, collecting the
+/// failures instead of stopping at the first one.
+///
+/// This is the entry point for indexers, bundling [`reconstruct_indexed`],
+/// deduplication and [`canonical_event_order`] as selected by `opts`.
+pub fn process_block_events(events: Vec<abci::Event>, opts: ProcessOptions) -> ProcessedBlock {
+    let mut processed = ProcessedBlock::default();
+    let mut seen = BTreeSet::new();
+
+    for (index, event) in events.into_iter().enumerate() {
+        if !opts.strict && !is_packet_event_kind(&event.kind) {
+            processed.skipped += 1;
+            continue;
+        }
+
+        match reconstruct_packet_event(event) {
+            Ok(event) => {
+                if opts.deduplicate && !seen.insert(hex_only_encoding(&event)) {
+                    processed.duplicates += 1;
+                    continue;
+                }
+                processed.events.push(event);
+            }
+            Err(e) => processed.errors.push((index, e)),
+        }
+    }
+
+    if opts.canonical_order {
+        processed.events = canonical_event_order(processed.events);
+    }
+
+    processed
+}
+
+fn is_packet_event_kind(kind: &str) -> bool {
+    matches!(
+        ChannelEvents::canonical_event_kind(kind),
+        ChannelEvents::SEND_PACKET_EVENT
+            | ChannelEvents::RECV_PACKET_EVENT
+            | ChannelEvents::WRITE_ACK_EVENT
+            | ChannelEvents::ACK_PACKET_EVENT
+            | ChannelEvents::TIMEOUT_EVENT
+    )
+}
+
+/// Sorts a batch of events into a canonical order, for chains committing to
+/// their event log.
+///
+/// Events carry no block position, so callers are expected to order events
+/// block by block. Within a batch, events are ordered by kind, then by the id
+/// of the packet they relate to, if any, and finally by the protobuf encoding
+/// of their [hex-only](IbcEvent::to_hex_only_abci_event) ABCI form, so that
+/// the result does not depend on the input order.
+pub fn canonical_event_order(mut events: Vec<IbcEvent>) -> Vec<IbcEvent> {
+    events.sort_by_cached_key(|event| {
+        (
+            event.event_type().to_string(),
+            packet_id(event),
+            hex_only_encoding(event),
+        )
+    });
+    events
+}
+
+/// The protobuf encoding of the hex-only ABCI form of an event, which, unlike
+/// [`to_cometbft_storage_bytes`], is available for every event.
+fn hex_only_encoding(event: &IbcEvent) -> Vec<u8> {
+    RawEvent::from(event.to_hex_only_abci_event()).encode_to_vec()
+}
+
+/// The id of the packet a packet event relates to.
+fn packet_id(event: &IbcEvent) -> Option<PacketId> {
+    let (port_id, channel_id, sequence) = match event {
+        IbcEvent::SendPacket(ev) => (ev.port_id_on_a(), ev.chan_id_on_a(), ev.seq_on_a()),
+        // The accessors of `ReceivePacket` are named from the point of view
+        // of the receiving chain, so `b` is the source here.
+        IbcEvent::ReceivePacket(ev) => (ev.port_id_on_b(), ev.chan_id_on_b(), ev.seq_on_b()),
+        IbcEvent::WriteAcknowledgement(ev) => (ev.port_id_on_a(), ev.chan_id_on_a(), ev.seq_on_a()),
+        IbcEvent::AcknowledgePacket(ev) => (ev.port_id_on_a(), ev.chan_id_on_a(), ev.seq_on_a()),
+        IbcEvent::TimeoutPacket(ev) => (ev.port_id_on_a(), ev.chan_id_on_a(), ev.seq_on_a()),
+        _ => return None,
+    };
+
+    Some(PacketId {
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        sequence: *sequence,
+    })
+}
+
+/// Serializes an [`IbcEvent`] into the protobuf encoding of the
+/// `tendermint.abci.Event` message, as CometBFT stores it in its block
+/// results.
+///
+/// Note that CometBFT does not commit to events: `LastResultsHash` only
+/// covers the deterministic fields of `ExecTxResult`, so these bytes cannot
+/// serve to prove the inclusion of an event.
+///
+/// Fails if the event cannot be converted into an [`abci::Event`], e.g. a
+/// packet event whose data is not valid UTF-8, which the deprecated plaintext
+/// attribute of the default [`EmissionProfile`] cannot represent.
+pub fn to_cometbft_storage_bytes(event: &IbcEvent) -> Result<Vec<u8>, Error> {
+    let abci_event: abci::Event = event.clone().try_into()?;
+
+    Ok(RawEvent::from(abci_event).encode_to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_core_channel_types::channel::Order;
+    use ibc_core_channel_types::events::mock_packet;
+    use ibc_core_channel_types::packet::Packet;
+    use ibc_core_channel_types::timeout::TimeoutHeight;
+    use ibc_core_client_types::Height;
+    use ibc_core_host_types::identifiers::ConnectionId;
+
+    use super::*;
+
+    fn send_event(packet: Packet) -> IbcEvent {
+        IbcEvent::SendPacket(ChannelEvents::SendPacket::new(
+            packet,
+            Order::Unordered,
+            ConnectionId::zero(),
+        ))
+    }
+
+    fn recv_event(packet: Packet) -> IbcEvent {
+        IbcEvent::ReceivePacket(ChannelEvents::ReceivePacket::new(
+            packet,
+            Order::Unordered,
+            ConnectionId::zero(),
+        ))
+    }
+
+    /// The mock packet of the given sequence, sent from `chan_id_on_a`.
+    fn packet_on(seq: u64, chan_id_on_a: ChannelId) -> Packet {
+        let mut packet = mock_packet(seq);
+        packet.chan_id_on_a = chan_id_on_a;
+        packet
+    }
+
+    /// The first mock packet, carrying `data`.
+    fn packet_with_data(data: Vec<u8>) -> Packet {
+        let mut packet = mock_packet(1);
+        packet.data = data;
+        packet
+    }
+
+    #[test]
+    fn reconstruct_preserves_indices() {
+        let send_packet =
+            ChannelEvents::SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero());
+        let recv_packet = ChannelEvents::ReceivePacket::new(
+            mock_packet(2),
+            Order::Unordered,
+            ConnectionId::zero(),
+        );
+        let events = vec![
+            send_packet.clone().try_into().unwrap(),
+            abci::Event {
+                kind: "transfer".to_string(),
+                attributes: vec![("amount", "100").into()],
+            },
+            recv_packet.clone().try_into().unwrap(),
+        ];
+
+        let results = reconstruct_indexed(events);
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(&results[0], (0, Ok(IbcEvent::SendPacket(ev))) if *ev == send_packet));
+        assert!(matches!(&results[1], (1, Err(_))));
+        assert!(matches!(&results[2], (2, Ok(IbcEvent::ReceivePacket(ev))) if *ev == recv_packet));
+    }
+
+    #[test]
+    fn message_event_cometbft_storage_bytes() {
+        let event = IbcEvent::Message(MessageEvent::Channel);
+
+        // Assembled by hand from the definition of `tendermint.abci.Event`:
+        // the `type` (field 1), then the `attributes` (field 2) with their
+        // `key` (1), `value` (2) and `index` (3).
+        let mut expected = vec![0x0a, 0x07];
+        expected.extend_from_slice(b"message");
+        expected.extend_from_slice(&[0x12, 0x17, 0x0a, 0x06]);
+        expected.extend_from_slice(b"module");
+        expected.extend_from_slice(&[0x12, 0x0b]);
+        expected.extend_from_slice(b"ibc_channel");
+        expected.extend_from_slice(&[0x18, 0x01]);
+
+        assert_eq!(to_cometbft_storage_bytes(&event).unwrap(), expected);
+    }
+
+    #[test]
+    fn remap_send_packet_timeout_revision() {
+        let mut packet = mock_packet(1);
+        packet.timeout_height_on_b = Height::new(4, 100).unwrap().into();
+        let mut event = send_event(packet);
+
+        remap_revision(&mut event, 4, 1);
+
+        let IbcEvent::SendPacket(send_packet) = event else {
+            panic!("event kind must be preserved");
+        };
+        assert_eq!(
+            send_packet.timeout_height_on_b(),
+            &TimeoutHeight::At(Height::new(1, 100).unwrap())
+        );
+    }
+
+    #[test]
+    fn send_packet_ibc_event_conversions() {
+        let send_packet =
+            ChannelEvents::SendPacket::new(mock_packet(1), Order::Ordered, ConnectionId::new(3));
+
+        let event = IbcEvent::from(send_packet.clone());
+        assert!(matches!(&event, IbcEvent::SendPacket(ev) if *ev == send_packet));
+
+        let extracted = ChannelEvents::SendPacket::try_from(event).unwrap();
+        assert_eq!(extracted.channel_ordering(), &Order::Ordered);
+        assert_eq!(extracted.conn_id_on_a(), &ConnectionId::new(3));
+        assert_eq!(extracted, send_packet);
+
+        let err = ChannelEvents::SendPacket::try_from(IbcEvent::Message(MessageEvent::Channel))
+            .unwrap_err();
+        assert!(matches!(err, Error::IncorrectEventType { event } if event == "message"));
+    }
+
+    #[test]
+    fn field_diff_of_changed_timeout() {
+        let packet = mock_packet(1);
+        let mut reorged_packet = packet.clone();
+        reorged_packet.timeout_height_on_b = Height::new(0, 42).unwrap().into();
+
+        let observed = send_event(packet);
+        let reobserved = send_event(reorged_packet);
+
+        assert!(event_field_diff(&observed, &observed).unwrap().is_empty());
+        assert_eq!(
+            event_field_diff(&observed, &reobserved).unwrap(),
+            vec![FieldChange {
+                field: "packet_timeout_height".to_string(),
+                previous: Some("0-0".to_string()),
+                current: Some("0-42".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn field_diff_of_changed_binary_data() {
+        let observed = send_event(packet_with_data(vec![0xff, 0x00]));
+        let reobserved = send_event(packet_with_data(vec![0xff, 0x01]));
+
+        assert_eq!(
+            event_field_diff(&observed, &reobserved).unwrap(),
+            vec![FieldChange {
+                field: "packet_data_hex".to_string(),
+                previous: Some("ff00".to_string()),
+                current: Some("ff01".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn field_diff_of_non_utf8_attribute() {
+        let send_packet =
+            ChannelEvents::SendPacket::new(mock_packet(1), Order::Unordered, ConnectionId::zero());
+        let observed = IbcEvent::SendPacket(send_packet.clone());
+        let reobserved = IbcEvent::SendPacket(send_packet.with_extra_attributes(vec![
+            abci::EventAttribute::V034(abci::v0_34::EventAttribute {
+                key: b"memo".to_vec(),
+                value: vec![0xff],
+                index: false,
+            }),
+        ]));
+
+        assert!(matches!(
+            event_field_diff(&observed, &reobserved),
+            Err(Error::Channel(
+                channel_error::ChannelError::InvalidAttributeValue { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn channel_throughput_of_multi_channel_batch() {
+        let events = vec![
+            send_event(packet_on(1, ChannelId::zero())),
+            send_event(packet_on(1, ChannelId::new(1))),
+            IbcEvent::Message(MessageEvent::Channel),
+            send_event(packet_on(2, ChannelId::zero())),
+            send_event(packet_on(3, ChannelId::zero())),
+            send_event(packet_on(2, ChannelId::new(1))),
+            send_event(packet_on(1, ChannelId::new(2))),
+        ];
+
+        let throughput = channel_throughput(&events);
+
+        assert_eq!(throughput.len(), 3);
+        assert_eq!(throughput[&(PortId::transfer(), ChannelId::zero())], 3);
+        assert_eq!(throughput[&(PortId::transfer(), ChannelId::new(1))], 2);
+        assert_eq!(throughput[&(PortId::transfer(), ChannelId::new(2))], 1);
+        assert!(channel_throughput(&[]).is_empty());
+    }
+
+    #[test]
+    fn canonical_order_of_shuffled_batch() {
+        let expected = vec![
+            IbcEvent::Message(MessageEvent::Channel),
+            recv_event(mock_packet(1)),
+            recv_event(mock_packet(2)),
+            send_event(packet_on(1, ChannelId::zero())),
+            send_event(packet_on(2, ChannelId::zero())),
+            send_event(packet_on(1, ChannelId::new(1))),
+        ];
+
+        let mut shuffled = expected.clone();
+        shuffled.swap(0, 5);
+        shuffled.swap(1, 3);
+        shuffled.swap(2, 4);
+        assert_ne!(shuffled, expected);
+
+        let mut reversed = expected.clone();
+        reversed.reverse();
+
+        assert_eq!(canonical_event_order(shuffled), expected);
+        assert_eq!(canonical_event_order(reversed), expected);
+        assert_eq!(canonical_event_order(expected.clone()), expected);
+    }
+
+    #[test]
+    fn canonical_order_keys_send_and_recv_by_source() {
+        let mut first = packet_on(1, ChannelId::zero());
+        first.chan_id_on_b = ChannelId::new(1);
+        let mut second = packet_on(1, ChannelId::new(1));
+        second.chan_id_on_b = ChannelId::zero();
+
+        assert_eq!(
+            packet_id(&send_event(first.clone())),
+            packet_id(&recv_event(first.clone()))
+        );
+
+        // Ordered by source channel, unlike their destination channels.
+        let expected = vec![
+            recv_event(first.clone()),
+            recv_event(second.clone()),
+            send_event(first),
+            send_event(second),
+        ];
+        let mut reversed = expected.clone();
+        reversed.reverse();
+
+        assert_eq!(canonical_event_order(reversed), expected);
+    }
+
+    #[test]
+    fn canonical_order_of_binary_packets() {
+        let send = |data: Vec<u8>| send_event(packet_with_data(data));
+
+        let batch = vec![send(vec![0xff, 0x01]), send(vec![0xff, 0x00])];
+        let mut reversed = batch.clone();
+        reversed.reverse();
+
+        let ordered = canonical_event_order(batch);
+        assert_eq!(
+            ordered,
+            vec![send(vec![0xff, 0x00]), send(vec![0xff, 0x01])]
+        );
+        assert_eq!(canonical_event_order(reversed), ordered);
+    }
+
+    #[test]
+    fn process_block_of_binary_packets() {
+        let send = |data: Vec<u8>| {
+            ChannelEvents::SendPacket::new(
+                packet_with_data(data),
+                Order::Unordered,
+                ConnectionId::zero(),
+            )
+            .to_hex_only_event()
+        };
+
+        let processed = process_block_events(
+            vec![
+                send(vec![0xff, 0x00]),
+                send(vec![0xff, 0x01]),
+                send(vec![0xff, 0x00]),
+            ],
+            ProcessOptions {
+                deduplicate: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(processed.errors.is_empty());
+        assert_eq!(processed.events.len(), 2);
+        assert_eq!(processed.duplicates, 1);
+    }
+
+    #[test]
+    fn process_realistic_block() {
+        let packet_event = |event: IbcEvent| abci::Event::try_from(event).unwrap();
+        let send = |seq: u64| packet_event(send_event(mock_packet(seq)));
+        let recv = |seq: u64| packet_event(recv_event(mock_packet(seq)));
+        let coin_spent = abci::Event {
+            kind: "coin_spent".to_string(),
+            attributes: vec![("amount", "100stake", true).into()],
+        };
+        let mut malformed = send(3);
+        malformed
+            .attributes
+            .retain(|attr| attr.key_str().ok() != Some("packet_sequence"));
+
+        let block = vec![
+            coin_spent.clone(),
+            packet_event(IbcEvent::Message(MessageEvent::Channel)),
+            send(2),
+            send(1),
+            coin_spent,
+            recv(1),
+            send(1),
+            malformed,
+        ];
+
+        let lenient = process_block_events(block.clone(), ProcessOptions::default());
+        assert_eq!(lenient.events.len(), 4);
+        assert_eq!(lenient.skipped, 3);
+        assert_eq!(lenient.duplicates, 0);
+        assert_eq!(lenient.errors.len(), 1);
+        assert_eq!(lenient.errors[0].0, 7);
+
+        let processed = process_block_events(
+            block.clone(),
+            ProcessOptions {
+                strict: false,
+                deduplicate: true,
+                canonical_order: true,
+            },
+        );
+        let expected: Vec<IbcEvent> = vec![
+            recv(1).try_into().map(IbcEvent::ReceivePacket).unwrap(),
+            send(1).try_into().map(IbcEvent::SendPacket).unwrap(),
+            send(2).try_into().map(IbcEvent::SendPacket).unwrap(),
+        ];
+        assert_eq!(processed.events, expected);
+        assert_eq!(processed.duplicates, 1);
+        assert_eq!(processed.skipped, 3);
+        assert_eq!(processed.errors.len(), 1);
+
+        let strict = process_block_events(
+            block,
+            ProcessOptions {
+                strict: true,
+                ..ProcessOptions::default()
+            },
+        );
+        assert_eq!(strict.skipped, 0);
+        let failed: Vec<usize> = strict.errors.iter().map(|(index, _)| *index).collect();
+        assert_eq!(failed, vec![0, 1, 4, 7]);
+    }
+}
@@ -13,6 +13,8 @@
     rust_2018_idioms
 )]
 
+extern crate alloc;
+
 #[cfg(feature = "std")]
 extern crate std;
 
@@ -66,6 +66,9 @@ fn send_packet_processing() {
     packet.seq_on_a = 1.into();
     packet.data = vec![0];
 
+    let mut packet_at_max_sequence = packet.clone();
+    packet_at_max_sequence.seq_on_a = u64::MAX.into();
+
     let mut packet_with_timestamp_old: Packet =
         dummy_raw_packet(timeout_height_future, timestamp_ns_past)
             .try_into()
@@ -112,6 +115,19 @@ fn send_packet_processing() {
             packet,
             want_pass: true,
         },
+        Test {
+            name: "Processing fails because the next send sequence would overflow".to_string(),
+            ctx: MockContext::default()
+                .with_light_client(
+                    &ClientId::new("07-tendermint", 0).expect("no error"),
+                    LightClientState::<MockHost>::with_latest_height(client_height),
+                )
+                .with_connection(ConnectionId::zero(), conn_end_on_a.clone())
+                .with_channel(PortId::transfer(), ChannelId::zero(), chan_end_on_a.clone())
+                .with_send_sequence(PortId::transfer(), ChannelId::zero(), u64::MAX.into()),
+            packet: packet_at_max_sequence,
+            want_pass: false,
+        },
         Test {
             name: "Packet timeout height same as destination chain height".to_string(),
             ctx: MockContext::default()